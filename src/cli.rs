@@ -1,5 +1,7 @@
 mod args;
 mod output;
+mod prompt;
 
 pub use args::*;
 pub use output::*;
+pub use prompt::*;
@@ -5,6 +5,10 @@ use clap::{Parser, Subcommand};
 #[command(version = "0.1.0")]
 #[command(about = "Cashflow planning for recurring payments", long_about = None)]
 pub struct Cli {
+    /// Use a named profile instead of the active one
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -20,14 +24,105 @@ pub enum Commands {
         /// Show past transactions
         #[arg(short, long)]
         past: bool,
+
+        /// Apply a what-if scenario and compare it with the real plan
+        #[arg(short, long)]
+        scenario: Option<String>,
+    },
+
+    /// Compute the transfer needed to stay above the warning threshold
+    Topup {
+        /// Number of days to look ahead (default: 30)
+        #[arg(short, long, default_value_t = 30)]
+        days: i64,
+
+        /// Minimum balance to keep (default: warning threshold of the profile)
+        #[arg(short, long, allow_negative_numbers = true)]
+        threshold: Option<String>,
+
+        /// Spread the top-up into at most one transfer per month
+        #[arg(short, long)]
+        monthly: bool,
+
+        /// Add the transfers as one-time transactions
+        #[arg(long)]
+        create: bool,
+
+        /// Description of the created transactions
+        #[arg(long, default_value = "Top-up")]
+        description: String,
     },
 
+    /// Estimate the risk of a low balance by simulating variable amounts
+    Risk {
+        /// Number of days to look ahead (default: 90)
+        #[arg(short, long, default_value_t = 90)]
+        days: i64,
+
+        /// Number of simulated runs (default: 1000)
+        #[arg(short, long, default_value_t = 1000)]
+        runs: usize,
+
+        /// Move recurring payments randomly by up to this many days
+        #[arg(short, long, default_value_t = 0)]
+        jitter: i64,
+
+        /// Seed of the random generator, for reproducible results
+        #[arg(long)]
+        seed: Option<u64>,
+
+        /// Minimum balance to keep (default: warning threshold of the profile)
+        #[arg(short, long, allow_negative_numbers = true)]
+        threshold: Option<String>,
+    },
+
+    /// Show monthly and per-category income and expense summaries
+    Report {
+        /// Number of past months including the current one (default: 12)
+        #[arg(short, long, default_value_t = 12)]
+        months: u32,
+
+        /// Number of projected months after the current one (default: 3)
+        #[arg(short, long, default_value_t = 3)]
+        ahead: u32,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Audit active recurring expenses: monthly and yearly cost, duplicates
+    #[command(alias = "subs")]
+    Subscriptions,
+
+    /// Add a one-time or recurring transaction step by step, with a preview of the plan
+    Add,
+
     /// Manage account balance
     Balance {
         #[command(subcommand)]
         action: BalanceAction,
     },
 
+    /// Manage account interest and fee rules
+    Account {
+        #[command(subcommand)]
+        action: AccountAction,
+    },
+
+    /// Manage spending limits per category
+    Budget {
+        #[command(subcommand)]
+        action: BudgetAction,
+    },
+
+    /// Manage savings goals (lists goals without a subcommand)
+    #[command(alias = "goal")]
+    Goals {
+        #[command(subcommand)]
+        action: Option<GoalAction>,
+    },
+
     /// Manage recurring transactions
     #[command(alias = "rec")]
     Recurring {
@@ -35,6 +130,12 @@ pub enum Commands {
         action: RecurringAction,
     },
 
+    /// Manage loans paid in monthly installments
+    Loan {
+        #[command(subcommand)]
+        action: LoanAction,
+    },
+
     /// Manage one-time transactions
     #[command(alias = "one")]
     OneTime {
@@ -42,6 +143,54 @@ pub enum Commands {
         action: OneTimeAction,
     },
 
+    /// Manage what-if scenarios
+    #[command(alias = "sc")]
+    Scenario {
+        #[command(subcommand)]
+        action: ScenarioAction,
+    },
+
+    /// Check alert rules, exit with 1 on warnings and 2 on a negative balance (for cron)
+    Check {
+        /// Number of days to look ahead (default: 30)
+        #[arg(short, long, default_value_t = 30)]
+        days: i64,
+
+        /// Minimum balance to keep (default: warning threshold of the profile)
+        #[arg(short, long, allow_negative_numbers = true)]
+        threshold: Option<String>,
+
+        /// Report single upcoming debits of at least this amount
+        #[arg(short, long)]
+        large_debit: Option<String>,
+
+        /// Report a balance snapshot older than this many days (default: from the profile, 14)
+        #[arg(short, long)]
+        stale_days: Option<i64>,
+
+        /// Only print the result, do not run the notifiers from the config
+        #[arg(long)]
+        no_notify: bool,
+    },
+
+    /// Print Prometheus metrics: balance, projected minimum, days until below threshold
+    Metrics {
+        /// Number of days to look ahead (default: 90)
+        #[arg(short, long, default_value_t = 90)]
+        days: i64,
+    },
+
+    /// Serve a local REST API with the plan and transactions as JSON
+    Serve {
+        /// Address to listen on
+        #[arg(short, long, default_value = "127.0.0.1:8080")]
+        bind: String,
+
+        /// Bearer token required by /api endpoints (default: CASHFLOW_API_TOKEN)
+        #[arg(long)]
+        token: Option<String>,
+    },
+
     /// Export data
     Export {
         /// Format: csv, json
@@ -49,6 +198,25 @@ pub enum Commands {
         format: String,
     },
 
+    /// Archive past one-time transactions and superseded balance snapshots
+    Prune {
+        /// Archive entries before this date (format: DD.MM.YYYY or YYYY-MM-DD),
+        /// defaults to the latest balance snapshot
+        #[arg(long)]
+        before: Option<String>,
+
+        /// Only show what would be archived
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Check the data file for problems
+    Doctor {
+        /// Automatically fix problems that have a safe resolution
+        #[arg(long)]
+        fix: bool,
+    },
+
     /// Manage configuration
     #[command(alias = "conf")]
     Config {
@@ -67,6 +235,59 @@ pub enum ConfigAction {
         /// Path to data directory
         path: String,
     },
+
+    /// Automatically archive past entries older than N days
+    SetAutoPrune {
+        /// Number of days, or "off" to disable
+        days: String,
+    },
+
+    /// Manage named profiles (separate data sets)
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ProfileAction {
+    /// Add a profile
+    Add {
+        /// Profile name
+        name: String,
+
+        /// Path to data directory of the profile
+        #[arg(long)]
+        data_dir: String,
+
+        /// Warn when the balance drops below this amount (default: 10 000)
+        #[arg(long, allow_negative_numbers = true)]
+        warning_threshold: Option<String>,
+
+        /// Show past transactions in the plan by default
+        #[arg(long)]
+        show_past: bool,
+
+        /// Warn when the balance snapshot is older than this many days (default: 14)
+        #[arg(long)]
+        stale_snapshot_days: Option<i64>,
+    },
+
+    /// List profiles
+    List,
+
+    /// Make a profile active ("default" for the top-level settings)
+    Use {
+        /// Profile name
+        name: String,
+    },
+
+    /// Remove a profile (data directory is kept)
+    #[command(alias = "del")]
+    Remove {
+        /// Profile name
+        name: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -79,10 +300,150 @@ pub enum BalanceAction {
         /// Date (format: DD.MM.YYYY or YYYY-MM-DD), defaults to today
         #[arg(long)]
         date: Option<String>,
+
+        /// Balance was taken before the day's transactions (default: after them)
+        #[arg(long)]
+        before: bool,
     },
 
     /// Show current balance
     Show,
+
+    /// List all balance snapshots with the change between them
+    List {
+        /// Include snapshots moved to the archive by prune
+        #[arg(long)]
+        archived: bool,
+    },
+
+    /// Delete a balance snapshot
+    Delete {
+        /// Date of the snapshot (DD.MM.YYYY or YYYY-MM-DD) or its ID
+        target: String,
+    },
+
+    /// Compare consecutive snapshots with what the plan expected between them
+    Diff,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AccountAction {
+    /// Show interest and fee rules
+    Show,
+
+    /// Change interest and fee rules (0 disables a rule)
+    Set {
+        /// Annual interest on positive balance in percent
+        #[arg(long)]
+        interest_rate: Option<String>,
+
+        /// Annual interest on negative balance in percent
+        #[arg(long)]
+        overdraft_rate: Option<String>,
+
+        /// Day of month when interest is credited or charged (31 = last day)
+        #[arg(long)]
+        interest_day: Option<u8>,
+
+        /// Monthly account fee
+        #[arg(long)]
+        fee: Option<String>,
+
+        /// Day of month when the fee is charged
+        #[arg(long)]
+        fee_day: Option<u8>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BudgetAction {
+    /// Compare planned spending with the budgets
+    Show {
+        /// Number of periods to show, starting with the current one (default: 1)
+        #[arg(short, long, default_value_t = 1)]
+        periods: u32,
+    },
+
+    /// Add or replace the budget of a category
+    Set {
+        /// Category, as used with --category on transactions
+        category: String,
+
+        /// Maximum spending per period
+        limit: String,
+
+        /// Period: monthly, yearly
+        #[arg(short, long, default_value = "monthly")]
+        period: String,
+    },
+
+    /// Delete the budgets of a category
+    Delete {
+        /// Category
+        category: String,
+
+        /// Only the budget of this period: monthly, yearly
+        #[arg(short, long)]
+        period: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum GoalAction {
+    /// List goals with the monthly contribution they need
+    List,
+
+    /// Add a savings goal
+    Add {
+        /// Unique name of the goal
+        name: String,
+
+        /// Amount needed
+        #[arg(short, long)]
+        target: String,
+
+        /// Date the money is needed by (DD.MM.YYYY or YYYY-MM-DD)
+        #[arg(long)]
+        date: String,
+
+        /// Money already set aside for the goal in the account
+        #[arg(short, long)]
+        earmarked: Option<String>,
+    },
+
+    /// Edit a savings goal
+    Edit {
+        /// Name of the goal
+        name: String,
+
+        /// New amount needed
+        #[arg(short, long)]
+        target: Option<String>,
+
+        /// New target date (DD.MM.YYYY or YYYY-MM-DD)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Money set aside for the goal in the account
+        #[arg(short, long)]
+        earmarked: Option<String>,
+    },
+
+    /// Create or adjust the monthly transfer that reaches the goal in time
+    Fund {
+        /// Name of the goal
+        name: String,
+
+        /// Day of month of the transfer (default: day of the current transfer, or 1)
+        #[arg(long)]
+        day: Option<u8>,
+    },
+
+    /// Delete a goal together with its transfer
+    Delete {
+        /// Name of the goal
+        name: String,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -100,6 +461,18 @@ pub enum RecurringAction {
         /// Day of month (1-31)
         #[arg(long)]
         day: u8,
+
+        /// Lowest expected amount, for variable amounts like utilities
+        #[arg(long, requires = "max", allow_negative_numbers = true)]
+        min: Option<String>,
+
+        /// Highest expected amount, for variable amounts like utilities
+        #[arg(long, requires = "min", allow_negative_numbers = true)]
+        max: Option<String>,
+
+        /// Category for reports
+        #[arg(short, long)]
+        category: Option<String>,
     },
 
     /// List all recurring transactions
@@ -107,13 +480,21 @@ pub enum RecurringAction {
 
     /// Edit a recurring transaction
     Edit {
-        /// Transaction ID
+        /// Transaction ID, ID prefix, list number or description
         id: String,
 
-        /// New amount
+        /// New amount, applied from today on unless --effective or --rewrite is given
         #[arg(short, long, allow_negative_numbers = true)]
         amount: Option<String>,
 
+        /// Date the new amount takes effect (format: DD.MM.YYYY or YYYY-MM-DD)
+        #[arg(long, requires = "amount")]
+        effective: Option<String>,
+
+        /// Replace the amount for past occurrences too, dropping the amount history
+        #[arg(long, requires = "amount", conflicts_with = "effective")]
+        rewrite: bool,
+
         /// New day of month
         #[arg(long)]
         day: Option<u8>,
@@ -121,26 +502,147 @@ pub enum RecurringAction {
         /// New description
         #[arg(short, long)]
         description: Option<String>,
+
+        /// New lowest expected amount
+        #[arg(long, requires = "max", allow_negative_numbers = true)]
+        min: Option<String>,
+
+        /// New highest expected amount
+        #[arg(long, requires = "min", allow_negative_numbers = true)]
+        max: Option<String>,
+
+        /// Make the amount fixed again, dropping its range
+        #[arg(long, conflicts_with = "min")]
+        no_range: bool,
+
+        /// New category (empty to remove)
+        #[arg(short, long)]
+        category: Option<String>,
     },
 
     /// Disable a recurring transaction
     Disable {
-        /// Transaction ID
+        /// Transaction ID, ID prefix, list number or description
         id: String,
     },
 
     /// Enable a recurring transaction
     Enable {
-        /// Transaction ID
+        /// Transaction ID, ID prefix, list number or description
         id: String,
     },
 
     /// Delete a recurring transaction permanently
     #[command(alias = "del")]
     Delete {
-        /// Transaction ID
+        /// Transaction ID, ID prefix, list number or description
+        id: String,
+    },
+
+    /// Skip one occurrence, e.g. a month without the bill
+    Skip {
+        /// Transaction ID, ID prefix, list number or description
+        id: String,
+
+        /// Scheduled date of the occurrence (default: next occurrence)
+        #[arg(long)]
+        date: Option<String>,
+    },
+
+    /// Move one occurrence to another date
+    Move {
+        /// Transaction ID, ID prefix, list number or description
+        id: String,
+
+        /// Scheduled date of the occurrence (default: next occurrence)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// New date of the occurrence (format: DD.MM.YYYY or YYYY-MM-DD)
+        #[arg(long)]
+        to: String,
+    },
+
+    /// Change the amount of one occurrence only
+    Adjust {
+        /// Transaction ID, ID prefix, list number or description
+        id: String,
+
+        /// Scheduled date of the occurrence (default: next occurrence)
+        #[arg(long)]
+        date: Option<String>,
+
+        /// Amount of this occurrence
+        #[arg(short, long, allow_negative_numbers = true)]
+        amount: String,
+    },
+
+    /// Mark one occurrence as paid
+    Paid {
+        /// Transaction ID, ID prefix, list number or description
+        id: String,
+
+        /// Scheduled date of the occurrence (default: latest occurrence up to today)
+        #[arg(long)]
+        date: Option<String>,
+    },
+
+    /// Drop all changes to one occurrence
+    Reset {
+        /// Transaction ID, ID prefix, list number or description
+        id: String,
+
+        /// Scheduled date of the occurrence (default: next occurrence)
+        #[arg(long)]
+        date: Option<String>,
+    },
+
+    /// List upcoming and changed occurrences
+    Occurrences {
+        /// Transaction ID, ID prefix, list number or description
         id: String,
+
+        /// Number of months to list
+        #[arg(short, long, default_value = "6")]
+        months: u32,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum LoanAction {
+    /// Add a loan; its installments are projected until it is paid off
+    Add {
+        /// Description
+        #[arg(short, long)]
+        description: String,
+
+        /// Outstanding principal (installment is computed from it unless --amount is given)
+        #[arg(short, long, required_unless_present = "amount")]
+        principal: Option<String>,
+
+        /// Annual interest rate in percent
+        #[arg(short, long, default_value = "0")]
+        rate: String,
+
+        /// Number of remaining installments
+        #[arg(short = 'n', long, alias = "term")]
+        installments: u32,
+
+        /// Fixed installment amount, if known from the lender
+        #[arg(short, long, allow_negative_numbers = true)]
+        amount: Option<String>,
+
+        /// Day of month of the installment (default: day of --first)
+        #[arg(long, required_unless_present = "first")]
+        day: Option<u8>,
+
+        /// Date of the next installment (format: DD.MM.YYYY or YYYY-MM-DD)
+        #[arg(long)]
+        first: Option<String>,
     },
+
+    /// List loans with remaining principal and payoff date
+    List,
 }
 
 #[derive(Subcommand, Debug)]
@@ -158,6 +660,10 @@ pub enum OneTimeAction {
         /// Date (format: DD.MM.YYYY or YYYY-MM-DD)
         #[arg(long)]
         date: String,
+
+        /// Category for reports
+        #[arg(short, long)]
+        category: Option<String>,
     },
 
     /// List one-time transactions
@@ -165,11 +671,15 @@ pub enum OneTimeAction {
         /// Show only upcoming transactions
         #[arg(long)]
         upcoming: bool,
+
+        /// Include archived (pruned) transactions
+        #[arg(long)]
+        archived: bool,
     },
 
     /// Edit a one-time transaction
     Edit {
-        /// Transaction ID
+        /// Transaction ID, ID prefix, list number or description
         id: String,
 
         /// New amount
@@ -183,12 +693,116 @@ pub enum OneTimeAction {
         /// New description
         #[arg(short, long)]
         description: Option<String>,
+
+        /// New category (empty to remove)
+        #[arg(short, long)]
+        category: Option<String>,
     },
 
     /// Delete a one-time transaction permanently
     #[command(alias = "del")]
     Delete {
-        /// Transaction ID
+        /// Transaction ID, ID prefix, list number or description
+        id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ScenarioAction {
+    /// Create an empty scenario
+    Create {
+        /// Scenario name
+        name: String,
+    },
+
+    /// List scenarios
+    List,
+
+    /// Show the changes of a scenario
+    Show {
+        /// Scenario name
+        name: String,
+    },
+
+    /// Delete a scenario
+    #[command(alias = "del")]
+    Delete {
+        /// Scenario name
+        name: String,
+    },
+
+    /// Add a recurring transaction that only exists in the scenario
+    AddRecurring {
+        /// Scenario name
+        name: String,
+
+        /// Description
+        #[arg(short, long)]
+        description: String,
+
+        /// Amount (positive for income, negative for expense)
+        #[arg(short, long, allow_negative_numbers = true)]
+        amount: String,
+
+        /// Day of month (1-31)
+        #[arg(long)]
+        day: u8,
+    },
+
+    /// Add a one-time transaction that only exists in the scenario
+    AddOneTime {
+        /// Scenario name
+        name: String,
+
+        /// Description
+        #[arg(short, long)]
+        description: String,
+
+        /// Amount (positive for income, negative for expense)
+        #[arg(short, long, allow_negative_numbers = true)]
+        amount: String,
+
+        /// Date (format: DD.MM.YYYY or YYYY-MM-DD)
+        #[arg(long)]
+        date: String,
+    },
+
+    /// Remove a recurring or one-time transaction in the scenario
+    Remove {
+        /// Scenario name
+        name: String,
+
+        /// Transaction ID, ID prefix or description
+        id: String,
+
+        /// Date the transaction is removed from (format: DD.MM.YYYY or YYYY-MM-DD, default today)
+        #[arg(long)]
+        effective: Option<String>,
+    },
+
+    /// Change the amount of a recurring or one-time transaction in the scenario
+    ChangeAmount {
+        /// Scenario name
+        name: String,
+
+        /// Transaction ID, ID prefix or description
         id: String,
+
+        /// New amount
+        #[arg(short, long, allow_negative_numbers = true)]
+        amount: String,
+
+        /// Date the new amount takes effect (format: DD.MM.YYYY or YYYY-MM-DD, default today)
+        #[arg(long)]
+        effective: Option<String>,
+    },
+
+    /// Drop a change by its number from 'scenario show'
+    DropChange {
+        /// Scenario name
+        name: String,
+
+        /// Change number
+        number: usize,
     },
 }
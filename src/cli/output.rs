@@ -1,14 +1,29 @@
-use crate::models::TransactionView;
-use crate::projection::CashflowProjection;
+use crate::models::{Goal, TransactionView};
+use crate::projection::{CashflowProjection, earmarked_on};
 use chrono::NaiveDate;
 use colored::*;
 use comfy_table::{Attribute, Cell, CellAlignment, Color, Table, presets::UTF8_FULL};
 use rust_decimal::Decimal;
+use std::collections::HashSet;
+use uuid::Uuid;
 
 /// Display options for the cashflow plan table
 pub struct PlanDisplayOptions {
     pub warning_threshold: Decimal,
     pub show_past: bool,
+    /// Snapshots older than this many days trigger a warning above the table
+    pub stale_snapshot_days: i64,
+    /// Transactions (ID, date) that push a category over its monthly budget
+    pub over_budget: HashSet<(Uuid, NaiveDate)>,
+    /// Goals whose earmarked money is subtracted in the available balance column
+    pub goals: Vec<Goal>,
+}
+
+impl PlanDisplayOptions {
+    /// True if some money is earmarked for goals and the available column is shown
+    fn shows_available(&self) -> bool {
+        self.goals.iter().any(|g| !g.earmarked.is_zero())
+    }
 }
 
 /// Formats a decimal amount as Czech currency (e.g., "22 158 Kč")
@@ -55,10 +70,11 @@ pub fn format_date(date: NaiveDate) -> String {
 fn add_transaction_row(
     table: &mut Table,
     txn: &TransactionView,
-    warning_threshold: Decimal,
+    options: &PlanDisplayOptions,
     min_balance: &mut Decimal,
     min_balance_date: &mut NaiveDate,
     is_past: bool,
+    show_worst_case: bool,
 ) {
     let amount_cell = Cell::new(format_amount(txn.amount))
         .set_alignment(CellAlignment::Right)
@@ -68,22 +84,25 @@ fn add_transaction_row(
             Color::Green
         });
 
-    let balance_cell = Cell::new(format_amount(txn.balance_after))
-        .set_alignment(CellAlignment::Right)
-        .fg(if txn.balance_after.is_sign_negative() {
-            Color::Red
-        } else if txn.balance_after < warning_threshold {
-            Color::Yellow
-        } else {
-            Color::Cyan
-        });
+    let warning_threshold = options.warning_threshold;
+    let balance_cell = colored_balance_cell(txn.balance_after, warning_threshold);
+    let over_budget = options.over_budget.contains(&(txn.source_id, txn.date));
 
     let mut description = txn.description.clone();
     if txn.is_one_time {
         description.push_str(" 💚");
     }
+    if txn.is_generated {
+        description.push_str(" 🏦");
+    }
+    if txn.confirmed {
+        description.push_str(" ✅");
+    }
+    if over_budget {
+        description.push_str(" 💸");
+    }
 
-    table.add_row(vec![
+    let mut row = vec![
         Cell::new(format_date(txn.date)).fg(if is_past {
             Color::DarkGrey
         } else {
@@ -91,41 +110,94 @@ fn add_transaction_row(
         }),
         Cell::new(description).fg(if is_past {
             Color::DarkGrey
+        } else if over_budget {
+            Color::Magenta
         } else {
             Color::White
         }),
         amount_cell,
         balance_cell,
-    ]);
+    ];
+    if show_worst_case {
+        row.push(colored_balance_cell(
+            txn.worst_balance_after,
+            warning_threshold,
+        ));
+    }
+    if options.shows_available() {
+        row.push(colored_balance_cell(
+            txn.balance_after - earmarked_on(&options.goals, txn.date),
+            warning_threshold,
+        ));
+    }
+    table.add_row(row);
 
-    // Track minimum balance
-    if txn.balance_after < *min_balance {
-        *min_balance = txn.balance_after;
+    // Track minimum balance, pessimistic when amounts vary
+    if txn.worst_balance_after < *min_balance {
+        *min_balance = txn.worst_balance_after;
         *min_balance_date = txn.date;
     }
 }
 
+/// Balance cell colored by sign and warning threshold
+fn colored_balance_cell(balance: Decimal, warning_threshold: Decimal) -> Cell {
+    Cell::new(format_amount(balance))
+        .set_alignment(CellAlignment::Right)
+        .fg(if balance.is_sign_negative() {
+            Color::Red
+        } else if balance < warning_threshold {
+            Color::Yellow
+        } else {
+            Color::Cyan
+        })
+}
+
 /// Prints the cashflow projection table
+/// With variable amounts, a worst-case balance column is added and the minimum balance
+/// summary uses the worst case
 pub fn print_plan_table(projection: &CashflowProjection, options: &PlanDisplayOptions) {
+    let show_worst_case = projection.has_amount_ranges();
+    let is_stale = projection.is_snapshot_stale(options.stale_snapshot_days);
+
+    if is_stale {
+        print_stale_snapshot_warning(projection);
+    }
+
+    let mut header = vec![
+        Cell::new("Datum").add_attribute(Attribute::Bold),
+        Cell::new("Popis").add_attribute(Attribute::Bold),
+        Cell::new("Částka").add_attribute(Attribute::Bold),
+        Cell::new("Zůstatek").add_attribute(Attribute::Bold),
+    ];
+    let snapshot_color = if is_stale {
+        Color::Yellow
+    } else {
+        Color::DarkGrey
+    };
+    let mut snapshot_row = vec![
+        Cell::new(format_date(projection.balance_snapshot.date)).fg(snapshot_color),
+        Cell::new("Nastavený zůstatek")
+            .fg(snapshot_color)
+            .add_attribute(Attribute::Bold),
+        Cell::new(""),
+        Cell::new(format_amount(projection.balance_snapshot.balance))
+            .set_alignment(CellAlignment::Right)
+            .fg(Color::Cyan),
+    ];
+    if show_worst_case {
+        header.push(Cell::new("V horším případě").add_attribute(Attribute::Bold));
+        snapshot_row.push(Cell::new(""));
+    }
+    if options.shows_available() {
+        header.push(Cell::new("Disponibilní").add_attribute(Attribute::Bold));
+        snapshot_row.push(Cell::new(""));
+    }
+
     let mut table = Table::new();
     table
         .load_preset(UTF8_FULL)
-        .set_header(vec![
-            Cell::new("Datum").add_attribute(Attribute::Bold),
-            Cell::new("Popis").add_attribute(Attribute::Bold),
-            Cell::new("Částka").add_attribute(Attribute::Bold),
-            Cell::new("Zůstatek").add_attribute(Attribute::Bold),
-        ])
-        .add_row(vec![
-            Cell::new(format_date(projection.balance_snapshot.date)).fg(Color::DarkGrey),
-            Cell::new("Nastavený zůstatek")
-                .fg(Color::DarkGrey)
-                .add_attribute(Attribute::Bold),
-            Cell::new(""),
-            Cell::new(format_amount(projection.balance_snapshot.balance))
-                .set_alignment(CellAlignment::Right)
-                .fg(Color::Cyan),
-        ]);
+        .set_header(header)
+        .add_row(snapshot_row);
 
     // Track minimum balance across all transactions
     let mut min_balance = projection.starting_balance;
@@ -136,33 +208,49 @@ pub fn print_plan_table(projection: &CashflowProjection, options: &PlanDisplayOp
             add_transaction_row(
                 &mut table,
                 txn,
-                options.warning_threshold,
+                options,
                 &mut min_balance,
                 &mut min_balance_date,
                 true,
+                show_worst_case,
             );
         }
     }
 
     // Then, add the current balance row
-    table.add_row(vec![
+    let mut current_row = vec![
         Cell::new(format_date(projection.start_date)),
         Cell::new("Současný zůstatek").add_attribute(Attribute::Bold),
         Cell::new(""),
         Cell::new(format_amount(projection.starting_balance))
             .set_alignment(CellAlignment::Right)
             .fg(Color::Cyan),
-    ]);
+    ];
+    if show_worst_case {
+        current_row.push(
+            Cell::new(format_amount(projection.starting_balance))
+                .set_alignment(CellAlignment::Right)
+                .fg(Color::Cyan),
+        );
+    }
+    if options.shows_available() {
+        current_row.push(colored_balance_cell(
+            projection.starting_balance - earmarked_on(&options.goals, projection.start_date),
+            options.warning_threshold,
+        ));
+    }
+    table.add_row(current_row);
 
     // Finally, add projected (future) transactions
     for txn in &projection.future_txns {
         add_transaction_row(
             &mut table,
             txn,
-            options.warning_threshold,
+            options,
             &mut min_balance,
             &mut min_balance_date,
             false,
+            show_worst_case,
         );
     }
 
@@ -181,15 +269,22 @@ pub fn print_plan_table(projection: &CashflowProjection, options: &PlanDisplayOp
         }
     }
 
+    let min_label = if show_worst_case {
+        "Nejnižší zůstatek (v horším případě)"
+    } else {
+        "Nejnižší zůstatek"
+    };
     if min_balance < options.warning_threshold {
         println!(
-            "Nejnižší zůstatek: {} ({})",
+            "{}: {} ({})",
+            min_label,
             format_amount(min_balance).yellow(),
             format_date(min_balance_date)
         );
     } else {
         println!(
-            "Nejnižší zůstatek: {} ({})",
+            "{}: {} ({})",
+            min_label,
             format_amount(min_balance),
             format_date(min_balance_date)
         );
@@ -197,12 +292,126 @@ pub fn print_plan_table(projection: &CashflowProjection, options: &PlanDisplayOp
 
     println!();
     println!("💚 = jednorázová transakce");
+    if projection
+        .past_txns
+        .iter()
+        .chain(&projection.future_txns)
+        .any(|t| t.is_generated)
+    {
+        println!("🏦 = úroky a poplatky podle pravidel účtu");
+    }
+    if projection
+        .past_txns
+        .iter()
+        .chain(&projection.future_txns)
+        .any(|t| t.confirmed)
+    {
+        println!("✅ = zaplacená platba");
+    }
+    if options.shows_available() {
+        println!("Disponibilní = zůstatek bez peněz vyhrazených na cíle");
+    }
+    if !options.over_budget.is_empty() {
+        println!("💸 = překročení měsíčního rozpočtu kategorie");
+    }
     println!(
         "⚠️  = zůstatek pod {}",
         format_amount(options.warning_threshold)
     );
 }
 
+/// Warns that the starting balance is extrapolated from an old snapshot
+pub fn print_stale_snapshot_warning(projection: &CashflowProjection) {
+    println!(
+        "{}",
+        format!(
+            "⚠️  Zůstatek je nastavený před {} dny ({}), současný zůstatek je jen odhad.",
+            projection.snapshot_age_days,
+            format_date(projection.balance_snapshot.date)
+        )
+        .yellow()
+    );
+    let unconfirmed = projection.past_txns.iter().filter(|t| !t.confirmed).count();
+    if unconfirmed > 0 {
+        println!(
+            "{}",
+            format!(
+                "    Nepotvrzené transakce započtené od té doby: {} (zobrazíte je přes --past).",
+                unconfirmed
+            )
+            .yellow()
+        );
+    }
+    println!(
+        "{}",
+        "    Nastavte aktuální zůstatek: cashflow balance set <částka>".yellow()
+    );
+    println!();
+}
+
+/// Prints a side-by-side comparison of the base plan and a scenario
+pub fn print_scenario_comparison(
+    base: &CashflowProjection,
+    scenario: &CashflowProjection,
+    scenario_name: &str,
+) {
+    let (base_min, base_min_date) = base.min_balance();
+    let (scenario_min, scenario_min_date) = scenario.min_balance();
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL).set_header(vec![
+        Cell::new("").add_attribute(Attribute::Bold),
+        Cell::new("Základ").add_attribute(Attribute::Bold),
+        Cell::new(format!("Scénář: {}", scenario_name)).add_attribute(Attribute::Bold),
+        Cell::new("Rozdíl").add_attribute(Attribute::Bold),
+    ]);
+
+    let rows = [
+        (
+            "Nejnižší zůstatek",
+            base_min,
+            scenario_min,
+            Some((base_min_date, scenario_min_date)),
+        ),
+        (
+            "Konečný zůstatek",
+            base.ending_balance(),
+            scenario.ending_balance(),
+            None,
+        ),
+    ];
+
+    for (label, base_value, scenario_value, dates) in rows {
+        let (base_text, scenario_text) = match dates {
+            Some((base_date, scenario_date)) => (
+                format!("{} ({})", format_amount(base_value), format_date(base_date)),
+                format!(
+                    "{} ({})",
+                    format_amount(scenario_value),
+                    format_date(scenario_date)
+                ),
+            ),
+            None => (format_amount(base_value), format_amount(scenario_value)),
+        };
+        let difference = scenario_value - base_value;
+
+        table.add_row(vec![
+            Cell::new(label).add_attribute(Attribute::Bold),
+            Cell::new(base_text).set_alignment(CellAlignment::Right),
+            Cell::new(scenario_text).set_alignment(CellAlignment::Right),
+            Cell::new(format_amount(difference))
+                .set_alignment(CellAlignment::Right)
+                .fg(if difference.is_sign_negative() {
+                    Color::Red
+                } else {
+                    Color::Green
+                }),
+        ]);
+    }
+
+    println!("{table}");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
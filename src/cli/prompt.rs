@@ -0,0 +1,59 @@
+use crate::Result;
+use anyhow::{Context, bail};
+use std::io::{BufRead, IsTerminal, Write};
+
+/// True if stdin is a terminal, so questions can be asked
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal()
+}
+
+/// Prints the question and reads one line of answer, trimmed
+pub fn prompt(question: &str) -> Result<String> {
+    print!("{}", question);
+    std::io::stdout()
+        .flush()
+        .context("Failed to write prompt")?;
+
+    let mut answer = String::new();
+    let read = std::io::stdin()
+        .lock()
+        .read_line(&mut answer)
+        .context("Failed to read answer")?;
+    if read == 0 {
+        bail!("No answer given");
+    }
+    Ok(answer.trim().to_string())
+}
+
+/// Asks until the answer parses, printing the error after each invalid answer
+/// An empty answer takes the default, if there is one
+pub fn prompt_with<T>(
+    question: &str,
+    default: Option<&str>,
+    parse: impl Fn(&str) -> Result<T>,
+) -> Result<T> {
+    loop {
+        let answer = prompt(question)?;
+        let answer = match (answer.is_empty(), default) {
+            (true, Some(default)) => default.to_string(),
+            _ => answer,
+        };
+        match parse(&answer) {
+            Ok(value) => return Ok(value),
+            Err(e) => println!("{}", e),
+        }
+    }
+}
+
+/// Asks a yes/no question, an empty answer takes the default
+pub fn confirm(question: &str, default: bool) -> Result<bool> {
+    prompt_with(
+        question,
+        Some(if default { "y" } else { "n" }),
+        |answer| match answer.to_lowercase().as_str() {
+            "y" | "yes" | "a" | "ano" => Ok(true),
+            "n" | "no" | "ne" => Ok(false),
+            _ => bail!("Answer y or n"),
+        },
+    )
+}
@@ -1,11 +1,43 @@
+mod account;
+mod add;
 mod balance;
+mod budget;
+mod check;
 mod config;
+mod doctor;
+mod goals;
+mod loan;
+mod metrics;
 mod one_time;
 mod plan;
+mod prune;
 mod recurring;
+mod report;
+mod resolve;
+mod risk;
+mod scenario;
+mod serve;
+mod subscriptions;
+mod topup;
 
+pub use account::*;
+pub use add::*;
 pub use balance::*;
+pub use budget::*;
+pub use check::*;
 pub use config::*;
+pub use doctor::*;
+pub use goals::*;
+pub use loan::*;
+pub use metrics::*;
 pub use one_time::*;
 pub use plan::*;
+pub use prune::*;
 pub use recurring::*;
+pub use report::*;
+pub use resolve::*;
+pub use risk::*;
+pub use scenario::*;
+pub use serve::*;
+pub use subscriptions::*;
+pub use topup::*;
@@ -0,0 +1,87 @@
+use crate::Result;
+use crate::cli::format_amount;
+use crate::commands::balance::parse_amount;
+use crate::models::AccountRules;
+use crate::storage::{load_data, save_data};
+
+/// Executes account show command
+pub async fn execute_account_show() -> Result<()> {
+    let data = load_data().await?;
+    print_rules(&data.account);
+
+    if data.account.is_empty() {
+        println!();
+        println!("Set them with e.g.:");
+        println!("  cashflow account set --interest-rate 3.5 --fee 99 --fee-day 1");
+    }
+
+    Ok(())
+}
+
+/// Executes account set command
+pub async fn execute_account_set(
+    interest_rate: Option<&str>,
+    overdraft_rate: Option<&str>,
+    interest_day: Option<u8>,
+    fee: Option<&str>,
+    fee_day: Option<u8>,
+) -> Result<()> {
+    let mut data = load_data().await?;
+    let rules = &mut data.account;
+
+    if let Some(rate) = interest_rate {
+        rules.interest_rate = parse_rate(rate)?;
+    }
+    if let Some(rate) = overdraft_rate {
+        rules.overdraft_rate = parse_rate(rate)?;
+    }
+    if let Some(day) = interest_day {
+        rules.interest_day = parse_day(day)?;
+    }
+    if let Some(fee) = fee {
+        // Fees are stored as a positive number whatever sign was typed
+        rules.monthly_fee = parse_amount(fee)?.abs();
+    }
+    if let Some(day) = fee_day {
+        rules.fee_day = parse_day(day)?;
+    }
+
+    let rules = rules.clone();
+    save_data(&data).await?;
+
+    println!("Account rules updated:");
+    print_rules(&rules);
+
+    Ok(())
+}
+
+fn print_rules(rules: &AccountRules) {
+    println!(
+        "  Interest: {} % p.a., credited on day {}",
+        rules.interest_rate, rules.interest_day
+    );
+    println!(
+        "  Overdraft interest: {} % p.a., charged on day {}",
+        rules.overdraft_rate, rules.interest_day
+    );
+    println!(
+        "  Monthly fee: {}, charged on day {}",
+        format_amount(rules.monthly_fee),
+        rules.fee_day
+    );
+}
+
+fn parse_rate(s: &str) -> Result<rust_decimal::Decimal> {
+    let rate = parse_amount(s)?;
+    if rate.is_sign_negative() {
+        anyhow::bail!("Interest rate must not be negative");
+    }
+    Ok(rate)
+}
+
+fn parse_day(day: u8) -> Result<u8> {
+    if !(1..=31).contains(&day) {
+        anyhow::bail!("Day of month must be 1-31");
+    }
+    Ok(day)
+}
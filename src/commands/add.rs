@@ -0,0 +1,300 @@
+use crate::Result;
+use crate::cli::{
+    confirm, format_amount, format_date, is_interactive, print_scenario_comparison, prompt,
+    prompt_with,
+};
+use crate::commands::balance::{parse_amount, parse_category, parse_date};
+use crate::config::get_display_settings;
+use crate::models::{CashflowData, OneTimeTransaction, RecurringTransaction};
+use crate::projection::project_cashflow;
+use crate::storage::{load_data, save_data};
+use anyhow::bail;
+use chrono::{Datelike, Local};
+use colored::Colorize;
+use rust_decimal::Decimal;
+use std::collections::BTreeSet;
+use uuid::Uuid;
+
+/// Number of days the preview compares
+const PREVIEW_DAYS: i64 = 90;
+
+/// Kind of transaction picked in the wizard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TransactionKind {
+    OneTime,
+    Recurring,
+}
+
+/// Transaction entered in the wizard
+#[derive(Debug, Clone)]
+enum NewTransaction {
+    OneTime(OneTimeTransaction),
+    Recurring(RecurringTransaction),
+}
+
+impl NewTransaction {
+    fn id(&self) -> Uuid {
+        match self {
+            NewTransaction::OneTime(txn) => txn.id,
+            NewTransaction::Recurring(txn) => txn.id,
+        }
+    }
+
+    fn description(&self) -> &str {
+        match self {
+            NewTransaction::OneTime(txn) => &txn.description,
+            NewTransaction::Recurring(txn) => &txn.description,
+        }
+    }
+
+    fn set_category(&mut self, category: Option<String>) {
+        match self {
+            NewTransaction::OneTime(txn) => txn.category = category,
+            NewTransaction::Recurring(txn) => txn.category = category,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        match self {
+            NewTransaction::OneTime(txn) => txn.validate()?,
+            NewTransaction::Recurring(txn) => txn.validate()?,
+        }
+        Ok(())
+    }
+
+    /// One line summary, e.g. "Netflix: -199 Kč every month on day 14"
+    fn summary(&self) -> String {
+        let (description, amount, schedule, category) = match self {
+            NewTransaction::OneTime(txn) => (
+                &txn.description,
+                txn.amount,
+                format!("on {}", format_date(txn.date)),
+                &txn.category,
+            ),
+            NewTransaction::Recurring(txn) => (
+                &txn.description,
+                txn.amount,
+                format!("every month on day {}", txn.day_of_month),
+                &txn.category,
+            ),
+        };
+        let category = category
+            .as_ref()
+            .map(|c| format!(" [{}]", c))
+            .unwrap_or_default();
+        format!(
+            "{}: {} {}{}",
+            description,
+            format_amount(amount),
+            schedule,
+            category
+        )
+    }
+
+    fn add_to(self, data: &mut CashflowData) {
+        match self {
+            NewTransaction::OneTime(txn) => data.one_time.push(txn),
+            NewTransaction::Recurring(txn) => data.recurring.push(txn),
+        }
+    }
+}
+
+/// Executes add command: asks for the transaction step by step, shows how it changes
+/// the plan and adds it after confirmation
+pub async fn execute_add() -> Result<()> {
+    if !is_interactive() {
+        bail!(
+            "'cashflow add' asks questions and needs a terminal. \
+             Use 'recurring add' or 'one-time add' in scripts."
+        );
+    }
+
+    let mut data = load_data().await?;
+    let today = Local::now().date_naive();
+
+    let kind = prompt_with(
+        "Type, [o]ne-time or [r]ecurring [o]: ",
+        Some("o"),
+        parse_kind,
+    )?;
+    let description = prompt_with("Description: ", None, parse_description)?;
+    let amount = prompt_with(
+        "Amount (negative for an expense, e.g. -1 500): ",
+        None,
+        parse_nonzero_amount,
+    )?;
+
+    let mut transaction = match kind {
+        TransactionKind::OneTime => {
+            let default = format_date(today);
+            let date = prompt_with(
+                &format!("Date (DD.MM.YYYY or YYYY-MM-DD) [{}]: ", default),
+                Some(&default),
+                parse_date,
+            )?;
+            NewTransaction::OneTime(OneTimeTransaction::new(description, amount, date))
+        }
+        TransactionKind::Recurring => {
+            let default = today.day().to_string();
+            let day = prompt_with(
+                &format!("Day of month (1-31) [{}]: ", default),
+                Some(&default),
+                parse_day,
+            )?;
+            NewTransaction::Recurring(RecurringTransaction::new(description, amount, day))
+        }
+    };
+
+    let categories = known_categories(&data);
+    let hint = if categories.is_empty() {
+        String::new()
+    } else {
+        format!(", used: {}", categories.join(", "))
+    };
+    let category = prompt(&format!("Category (optional{}): ", hint))?;
+    transaction.set_category(parse_category(Some(&category)));
+    transaction.validate()?;
+
+    println!();
+    println!("{}", transaction.summary());
+    print_preview(&data, &transaction).await?;
+    println!();
+
+    if !confirm("Add this transaction? [Y/n]: ", true)? {
+        println!("Nothing was added.");
+        return Ok(());
+    }
+
+    let id = transaction.id();
+    transaction.add_to(&mut data);
+    save_data(&data).await?;
+    println!("Added, ID: {}", id);
+
+    Ok(())
+}
+
+/// Compares the plan of the next days with and without the new transaction
+async fn print_preview(data: &CashflowData, transaction: &NewTransaction) -> Result<()> {
+    if data.balance_snapshots.is_empty() {
+        println!("Set the balance with 'balance set' to see how the plan changes.");
+        return Ok(());
+    }
+
+    let mut changed = data.clone();
+    transaction.clone().add_to(&mut changed);
+    let base = project_cashflow(data, PREVIEW_DAYS)?;
+    let projection = project_cashflow(&changed, PREVIEW_DAYS)?;
+
+    let id = transaction.id();
+    let occurrences: Vec<Decimal> = projection
+        .future_txns
+        .iter()
+        .filter(|t| t.source_id == id)
+        .map(|t| t.amount)
+        .collect();
+    println!(
+        "Payments in the next {} days: {}, {} in total",
+        PREVIEW_DAYS,
+        occurrences.len(),
+        format_amount(occurrences.iter().sum())
+    );
+    print_scenario_comparison(&base, &projection, transaction.description());
+
+    let threshold = get_display_settings().await?.warning_threshold;
+    let (min_balance, min_date) = projection.min_balance();
+    if min_balance < threshold && base.min_balance().0 >= threshold {
+        println!(
+            "{}",
+            format!(
+                "⚠️  The balance drops below {} on {}",
+                format_amount(threshold),
+                format_date(min_date)
+            )
+            .yellow()
+        );
+    }
+
+    Ok(())
+}
+
+fn parse_kind(s: &str) -> Result<TransactionKind> {
+    match s.to_lowercase().as_str() {
+        "o" | "one-time" | "onetime" | "one" => Ok(TransactionKind::OneTime),
+        "r" | "recurring" | "rec" => Ok(TransactionKind::Recurring),
+        _ => bail!("Answer o for one-time or r for recurring"),
+    }
+}
+
+fn parse_description(s: &str) -> Result<String> {
+    if s.is_empty() {
+        bail!("Description is required");
+    }
+    Ok(s.to_string())
+}
+
+fn parse_nonzero_amount(s: &str) -> Result<Decimal> {
+    let amount = parse_amount(s)?;
+    if amount.is_zero() {
+        bail!("Amount must not be zero");
+    }
+    Ok(amount)
+}
+
+fn parse_day(s: &str) -> Result<u8> {
+    match s.parse::<u8>() {
+        Ok(day) if (1..=31).contains(&day) => Ok(day),
+        _ => bail!("Day of month must be a number from 1 to 31"),
+    }
+}
+
+/// Categories already in use, offered as a hint
+fn known_categories(data: &CashflowData) -> Vec<String> {
+    data.recurring
+        .iter()
+        .filter_map(|t| t.category.clone())
+        .chain(data.one_time.iter().filter_map(|t| t.category.clone()))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(parse_kind("R").unwrap(), TransactionKind::Recurring);
+        assert_eq!(parse_kind("one-time").unwrap(), TransactionKind::OneTime);
+        assert!(parse_kind("x").is_err());
+
+        assert_eq!(parse_day("31").unwrap(), 31);
+        assert!(parse_day("0").is_err());
+        assert!(parse_day("32").is_err());
+
+        assert_eq!(
+            parse_nonzero_amount("-1 500").unwrap(),
+            Decimal::from(-1500)
+        );
+        assert!(parse_nonzero_amount("0").is_err());
+        assert!(parse_description("").is_err());
+    }
+
+    #[test]
+    fn test_summary() {
+        let mut txn = NewTransaction::Recurring(RecurringTransaction::new(
+            "Netflix".to_string(),
+            Decimal::from(-199),
+            14,
+        ));
+        txn.set_category(Some("Zábava".to_string()));
+        assert_eq!(
+            txn.summary(),
+            "Netflix: -199 Kč every month on day 14 [Zábava]"
+        );
+
+        let mut data = CashflowData::default();
+        txn.add_to(&mut data);
+        assert_eq!(known_categories(&data), vec!["Zábava".to_string()]);
+    }
+}
@@ -1,14 +1,24 @@
 use crate::Result;
-use crate::cli::format_amount;
-use crate::models::BalanceSnapshot;
-use crate::storage::{load_data, save_data};
+use crate::cli::{format_amount, format_date};
+use crate::commands::resolve::{IdCandidate, resolve_id_unnumbered};
+use crate::models::{BalanceSnapshot, SnapshotTiming};
+use crate::projection::snapshot_diffs;
+use crate::storage::{load_archive, load_data, load_data_with_archive, save_data};
 use anyhow::Context;
 use chrono::{Local, NaiveDate};
+use comfy_table::{Attribute, Cell, CellAlignment, Color, Table, presets::UTF8_FULL};
 use rust_decimal::Decimal;
+use std::collections::HashSet;
 use std::str::FromStr;
+use uuid::Uuid;
 
 /// Executes the balance set command
-pub async fn execute_balance_set(amount_str: &str, date_str: Option<&str>) -> Result<()> {
+/// With `before`, the balance was taken before the day's transactions, so they still apply
+pub async fn execute_balance_set(
+    amount_str: &str,
+    date_str: Option<&str>,
+    before: bool,
+) -> Result<()> {
     // Parse amount
     let amount = parse_amount(amount_str)?;
 
@@ -19,6 +29,12 @@ pub async fn execute_balance_set(amount_str: &str, date_str: Option<&str>) -> Re
         Local::now().date_naive()
     };
 
+    let timing = if before {
+        SnapshotTiming::Before
+    } else {
+        SnapshotTiming::After
+    };
+
     // Load data
     let mut data = load_data().await?;
 
@@ -26,18 +42,22 @@ pub async fn execute_balance_set(amount_str: &str, date_str: Option<&str>) -> Re
     if let Some(existing) = data.balance_snapshots.iter_mut().find(|s| s.date == date) {
         // Update existing snapshot
         existing.balance = amount;
+        existing.timing = timing;
         println!(
-            "Updated balance for {}: {}",
+            "Updated balance for {} ({}): {}",
             date.format("%d.%m.%Y"),
+            timing_label(timing),
             format_amount(amount)
         );
     } else {
         // Create new snapshot
-        let snapshot = BalanceSnapshot::new(date, amount);
+        let mut snapshot = BalanceSnapshot::new(date, amount);
+        snapshot.timing = timing;
         data.balance_snapshots.push(snapshot);
         println!(
-            "Set balance for {}: {}",
+            "Set balance for {} ({}): {}",
             date.format("%d.%m.%Y"),
+            timing_label(timing),
             format_amount(amount)
         );
     }
@@ -75,6 +95,200 @@ pub async fn execute_balance_show() -> Result<()> {
     Ok(())
 }
 
+/// Executes the balance list command: all snapshots with the change since the previous one
+/// With `archived`, pruned snapshots from the archive are listed as well
+pub async fn execute_balance_list(archived: bool) -> Result<()> {
+    let data = load_data().await?;
+
+    let mut snapshots = data.balance_snapshots.clone();
+    let mut archived_ids = HashSet::new();
+    if archived {
+        let archive = load_archive().await?;
+        archived_ids.extend(archive.balance_snapshots.iter().map(|s| s.id));
+        snapshots.extend(archive.balance_snapshots);
+    }
+
+    if snapshots.is_empty() {
+        println!("No balance snapshots found.");
+        println!("Set your current balance first:");
+        println!("  cashflow balance set <amount>");
+        return Ok(());
+    }
+
+    snapshots.sort_by_key(|s| s.date);
+    let latest_id = data
+        .balance_snapshots
+        .iter()
+        .max_by_key(|s| s.date)
+        .map(|s| s.id);
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec![
+        Cell::new("ID").add_attribute(Attribute::Bold),
+        Cell::new("Date").add_attribute(Attribute::Bold),
+        Cell::new("Balance").add_attribute(Attribute::Bold),
+        Cell::new("Change").add_attribute(Attribute::Bold),
+        Cell::new("").add_attribute(Attribute::Bold),
+    ]);
+
+    let mut previous: Option<Decimal> = None;
+    for snapshot in &snapshots {
+        let id_short = snapshot.id.to_string().chars().take(8).collect::<String>();
+        let change = previous.map(|p| snapshot.balance - p);
+        let change_cell = match change {
+            Some(change) => Cell::new(format_amount(change))
+                .set_alignment(CellAlignment::Right)
+                .fg(if change.is_sign_negative() {
+                    Color::Red
+                } else {
+                    Color::Green
+                }),
+            None => Cell::new(""),
+        };
+        let mut notes = Vec::new();
+        if snapshot.timing == SnapshotTiming::Before {
+            notes.push("before the day's transactions");
+        }
+        if archived_ids.contains(&snapshot.id) {
+            notes.push("archived");
+        } else if Some(snapshot.id) == latest_id {
+            notes.push("latest");
+        }
+
+        table.add_row(vec![
+            Cell::new(id_short),
+            Cell::new(format_date(snapshot.date)),
+            Cell::new(format_amount(snapshot.balance)).set_alignment(CellAlignment::Right),
+            change_cell,
+            Cell::new(notes.join(", ")),
+        ]);
+        previous = Some(snapshot.balance);
+    }
+
+    println!("{table}");
+    println!();
+    println!("Total: {} balance snapshots", snapshots.len());
+
+    Ok(())
+}
+
+/// Executes the balance delete command, the snapshot is given by its date or ID
+pub async fn execute_balance_delete(target: &str) -> Result<()> {
+    let mut data = load_data().await?;
+    let id = find_snapshot(target, &data.balance_snapshots)?;
+
+    let index = data
+        .balance_snapshots
+        .iter()
+        .position(|s| s.id == id)
+        .context("Balance snapshot not found")?;
+    let removed = data.balance_snapshots.remove(index);
+    save_data(&data).await?;
+
+    println!(
+        "Deleted balance snapshot for {}: {}",
+        format_date(removed.date),
+        format_amount(removed.balance)
+    );
+    match data.balance_snapshots.iter().max_by_key(|s| s.date) {
+        Some(latest) => println!(
+            "The plan now starts from {}: {}",
+            format_date(latest.date),
+            format_amount(latest.balance)
+        ),
+        None => {
+            println!("No balance snapshots left. Set your current balance with:");
+            println!("  cashflow balance set <amount>");
+        }
+    }
+
+    Ok(())
+}
+
+/// Executes the balance diff command: compares consecutive snapshots with the planned
+/// transactions between them to show how far reality drifted from the plan
+pub async fn execute_balance_diff() -> Result<()> {
+    // Archived snapshots and transactions are history too
+    let data = load_data_with_archive().await?;
+    let diffs = snapshot_diffs(&data);
+
+    if diffs.is_empty() {
+        println!("At least two balance snapshots are needed to compare them.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+    table.set_header(vec![
+        Cell::new("From").add_attribute(Attribute::Bold),
+        Cell::new("To").add_attribute(Attribute::Bold),
+        Cell::new("Planned txns").add_attribute(Attribute::Bold),
+        Cell::new("Expected").add_attribute(Attribute::Bold),
+        Cell::new("Actual").add_attribute(Attribute::Bold),
+        Cell::new("Drift").add_attribute(Attribute::Bold),
+    ]);
+
+    for diff in &diffs {
+        let drift = diff.drift();
+        let drift_color = if drift.is_zero() {
+            Color::Reset
+        } else if drift.is_sign_negative() {
+            Color::Red
+        } else {
+            Color::Green
+        };
+
+        table.add_row(vec![
+            Cell::new(format_date(diff.from_date)),
+            Cell::new(format_date(diff.to_date)),
+            Cell::new(diff.transactions).set_alignment(CellAlignment::Right),
+            Cell::new(format_amount(diff.expected)).set_alignment(CellAlignment::Right),
+            Cell::new(format_amount(diff.to_balance)).set_alignment(CellAlignment::Right),
+            Cell::new(format_amount(drift))
+                .set_alignment(CellAlignment::Right)
+                .fg(drift_color),
+        ]);
+    }
+
+    let total: Decimal = diffs.iter().map(|d| d.drift()).sum();
+    println!("{table}");
+    println!();
+    println!("Total drift: {}", format_amount(total));
+    println!("Drift = actual balance − balance expected by the plan (negative: less money)");
+
+    Ok(())
+}
+
+/// Describes whether a snapshot includes the transactions of its day
+fn timing_label(timing: SnapshotTiming) -> &'static str {
+    match timing {
+        SnapshotTiming::Before => "before the day's transactions",
+        SnapshotTiming::After => "after the day's transactions",
+    }
+}
+
+/// Finds a snapshot by date (DD.MM.YYYY or YYYY-MM-DD), ID or ID prefix
+fn find_snapshot(s: &str, snapshots: &[BalanceSnapshot]) -> Result<Uuid> {
+    if let Ok(date) = parse_date(s) {
+        return snapshots
+            .iter()
+            .find(|snapshot| snapshot.date == date)
+            .map(|snapshot| snapshot.id)
+            .with_context(|| {
+                format!(
+                    "No balance snapshot on {}. Use 'balance list' to see available snapshots.",
+                    format_date(date)
+                )
+            });
+    }
+
+    let mut ordered: Vec<_> = snapshots.iter().collect();
+    ordered.sort_by_key(|snapshot| snapshot.date);
+    let candidates: Vec<_> = ordered.into_iter().map(IdCandidate::snapshot).collect();
+    resolve_id_unnumbered(s, &candidates, "balance snapshot", "balance list")
+}
+
 /// Parses amount from string, supports both formats:
 /// - "22158" or "22 158" (without sign, positive)
 /// - "-478" or "- 478" (negative)
@@ -85,6 +299,13 @@ pub fn parse_amount(s: &str) -> Result<Decimal> {
     Decimal::from_str(&cleaned).context("Invalid amount format. Use: 22158 or -478")
 }
 
+/// Normalizes a category argument, empty or blank means no category
+pub fn parse_category(s: Option<&str>) -> Option<String> {
+    s.map(str::trim)
+        .filter(|c| !c.is_empty())
+        .map(str::to_string)
+}
+
 /// Parses date from string, supports formats:
 /// - "DD.MM.YYYY" (Czech format)
 /// - "YYYY-MM-DD" (ISO format)
@@ -126,6 +347,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_find_snapshot() {
+        let first = BalanceSnapshot::new(
+            NaiveDate::from_ymd_opt(2025, 1, 10).unwrap(),
+            Decimal::from(1000),
+        );
+        let second = BalanceSnapshot::new(
+            NaiveDate::from_ymd_opt(2025, 2, 10).unwrap(),
+            Decimal::from(2000),
+        );
+        let prefix: String = second.id.to_string().chars().take(8).collect();
+        let snapshots = vec![first.clone(), second.clone()];
+
+        assert_eq!(find_snapshot("10.01.2025", &snapshots).unwrap(), first.id);
+        assert_eq!(find_snapshot("2025-02-10", &snapshots).unwrap(), second.id);
+        assert_eq!(find_snapshot(&prefix, &snapshots).unwrap(), second.id);
+        assert!(find_snapshot("11.01.2025", &snapshots).is_err());
+        assert!(find_snapshot("abc", &snapshots).is_err());
+        assert!(find_snapshot("2", &snapshots).is_err());
+    }
+
     #[test]
     fn test_parse_date() {
         let expected = NaiveDate::from_ymd_opt(2025, 10, 13).unwrap();
@@ -0,0 +1,142 @@
+use crate::Result;
+use crate::cli::format_amount;
+use crate::commands::balance::parse_amount;
+use crate::models::{Budget, BudgetPeriod};
+use crate::projection::budget_statuses;
+use crate::storage::{load_data, load_data_with_archive, save_data};
+use chrono::Local;
+use comfy_table::{Attribute, Cell, CellAlignment, Color, Table, presets::UTF8_FULL};
+use rust_decimal::Decimal;
+
+/// Executes budget show command: planned spending per category against its budget
+/// Archived transactions count too, the current period may have started before the
+/// latest prune
+pub async fn execute_budget_show(periods: u32) -> Result<()> {
+    let data = load_data_with_archive().await?;
+
+    if data.budgets.is_empty() {
+        println!("No budgets found.");
+        println!("Add one with:");
+        println!("  cashflow budget set <category> <limit> [--period monthly|yearly]");
+        return Ok(());
+    }
+
+    let today = Local::now().date_naive();
+    let statuses = budget_statuses(&data, today, periods);
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+
+    table.set_header(vec![
+        Cell::new("Category").add_attribute(Attribute::Bold),
+        Cell::new("Period").add_attribute(Attribute::Bold),
+        Cell::new("Limit").add_attribute(Attribute::Bold),
+        Cell::new("Planned").add_attribute(Attribute::Bold),
+        Cell::new("Remaining").add_attribute(Attribute::Bold),
+        Cell::new("Used").add_attribute(Attribute::Bold),
+    ]);
+
+    for status in &statuses {
+        let used = if status.budget.limit.is_zero() {
+            String::from("-")
+        } else {
+            format!(
+                "{}%",
+                (status.planned * Decimal::from(100) / status.budget.limit).round()
+            )
+        };
+        let color = if status.is_over() {
+            Color::Red
+        } else {
+            Color::Green
+        };
+
+        table.add_row(vec![
+            Cell::new(&status.budget.category),
+            Cell::new(format!(
+                "{} – {}",
+                status.period_start.format("%d.%m.%Y"),
+                status.period_end.format("%d.%m.%Y")
+            )),
+            Cell::new(format_amount(status.budget.limit)).set_alignment(CellAlignment::Right),
+            Cell::new(format_amount(status.planned)).set_alignment(CellAlignment::Right),
+            Cell::new(format_amount(status.remaining()))
+                .set_alignment(CellAlignment::Right)
+                .fg(color),
+            Cell::new(used)
+                .set_alignment(CellAlignment::Right)
+                .fg(color),
+        ]);
+    }
+
+    println!("{table}");
+
+    let over = statuses.iter().filter(|s| s.is_over()).count();
+    if over > 0 {
+        println!();
+        println!("⚠ {} budget period(s) overspent", over);
+    }
+
+    Ok(())
+}
+
+/// Executes budget set command: adds or replaces the budget of a category and period
+pub async fn execute_budget_set(category: &str, limit_str: &str, period_str: &str) -> Result<()> {
+    let category = category.trim();
+    if category.is_empty() {
+        anyhow::bail!("Category must not be empty");
+    }
+    let period: BudgetPeriod = period_str.parse()?;
+    // Limits are stored as a positive number whatever sign was typed
+    let limit = parse_amount(limit_str)?.abs();
+
+    let mut data = load_data().await?;
+    let budget = Budget {
+        category: category.to_string(),
+        period,
+        limit,
+    };
+
+    match data
+        .budgets
+        .iter_mut()
+        .find(|b| b.period == period && b.matches(Some(category)))
+    {
+        Some(existing) => *existing = budget,
+        None => data.budgets.push(budget),
+    }
+
+    save_data(&data).await?;
+
+    println!(
+        "Budget set: {} {} ({})",
+        category,
+        format_amount(limit),
+        period
+    );
+
+    Ok(())
+}
+
+/// Executes budget delete command; without a period all budgets of the category are removed
+pub async fn execute_budget_delete(category: &str, period_str: Option<&str>) -> Result<()> {
+    let period: Option<BudgetPeriod> = period_str.map(str::parse).transpose()?;
+
+    let mut data = load_data().await?;
+    let before = data.budgets.len();
+    data.budgets
+        .retain(|b| !(b.matches(Some(category)) && period.is_none_or(|p| p == b.period)));
+
+    let removed = before - data.budgets.len();
+    if removed == 0 {
+        anyhow::bail!(
+            "No budget found for category '{}'. Use 'budget show' to see all budgets.",
+            category
+        );
+    }
+
+    save_data(&data).await?;
+    println!("Deleted {} budget(s) of category {}", removed, category);
+
+    Ok(())
+}
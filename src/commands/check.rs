@@ -0,0 +1,280 @@
+use crate::Result;
+use crate::cli::{format_amount, format_date};
+use crate::commands::balance::parse_amount;
+use crate::config::{
+    NotifySettings, SmtpSecurity, SmtpSettings, get_display_settings, get_notify_settings,
+};
+use crate::projection::{
+    Alert, CheckRules, CheckStatus, evaluate_rules, overall_status, project_cashflow,
+};
+use crate::storage::load_data;
+use anyhow::{Context, bail};
+use serde::Serialize;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+
+/// How long a webhook or mail server may take before the notification fails
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Body of the webhook request
+#[derive(Serialize)]
+struct AlertPayload<'a> {
+    status: CheckStatus,
+    message: &'a str,
+    alerts: &'a [Alert],
+}
+
+/// Executes check command: evaluates the alert rules, prints a short summary and
+/// sends it to the configured notifiers when a rule fails
+/// Returns the overall status, its exit code is used as the exit code of the process
+pub async fn execute_check(
+    days: i64,
+    threshold: Option<&str>,
+    large_debit: Option<&str>,
+    stale_days: Option<i64>,
+    notify: bool,
+) -> Result<CheckStatus> {
+    let data = load_data().await?;
+    let display = get_display_settings().await?;
+
+    let threshold = match threshold {
+        Some(threshold) => parse_amount(threshold)?,
+        None => display.warning_threshold,
+    };
+    let rules = CheckRules {
+        threshold,
+        large_debit: large_debit.map(parse_amount).transpose()?.map(|a| a.abs()),
+        stale_after_days: Some(stale_days.unwrap_or(display.stale_snapshot_days)),
+    };
+
+    let projection = project_cashflow(&data, days)?;
+    let alerts = evaluate_rules(&projection, &rules);
+    let status = overall_status(&alerts);
+
+    if alerts.is_empty() {
+        println!(
+            "{}: balance stays above {} for the next {} days",
+            status,
+            format_amount(threshold),
+            days
+        );
+        return Ok(status);
+    }
+
+    let message = format_alerts(&alerts);
+    print!("{}", message);
+
+    if notify {
+        let settings = get_notify_settings().await?;
+        send_notifications(&settings, status, &message, &alerts).await;
+    }
+
+    Ok(status)
+}
+
+/// One line per alert, prefixed with its severity
+fn format_alerts(alerts: &[Alert]) -> String {
+    alerts
+        .iter()
+        .map(|alert| format!("{}: {}\n", alert.status(), describe_alert(alert)))
+        .collect()
+}
+
+fn describe_alert(alert: &Alert) -> String {
+    match alert {
+        Alert::NegativeBalance { date, balance } => format!(
+            "balance goes negative on {} ({})",
+            format_date(*date),
+            format_amount(*balance)
+        ),
+        Alert::BelowThreshold {
+            date,
+            balance,
+            threshold,
+        } => format!(
+            "balance drops below {} on {} ({})",
+            format_amount(*threshold),
+            format_date(*date),
+            format_amount(*balance)
+        ),
+        Alert::LargeDebit {
+            date,
+            description,
+            amount,
+        } => format!(
+            "large debit on {}: {} {}",
+            format_date(*date),
+            description,
+            format_amount(*amount)
+        ),
+        Alert::StaleSnapshot {
+            date,
+            age_days,
+            max_age_days,
+        } => format!(
+            "balance snapshot from {} is {} days old (limit {})",
+            format_date(*date),
+            age_days,
+            max_age_days
+        ),
+    }
+}
+
+/// Sends the message to every configured notifier
+/// A failing notifier is reported on stderr and does not stop the others
+async fn send_notifications(
+    settings: &NotifySettings,
+    status: CheckStatus,
+    message: &str,
+    alerts: &[Alert],
+) {
+    if let Some(command) = &settings.command
+        && let Err(e) = run_command(command, status, message).await
+    {
+        eprintln!("Notification command failed: {:#}", e);
+    }
+
+    if let Some(url) = &settings.webhook {
+        let url = url.clone();
+        let body = serde_json::to_value(AlertPayload {
+            status,
+            message,
+            alerts,
+        });
+        let sent = match body {
+            Ok(body) => tokio::task::spawn_blocking(move || post_webhook(&url, &body))
+                .await
+                .map_err(anyhow::Error::from)
+                .and_then(|r| r),
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = sent {
+            eprintln!("Webhook notification failed: {:#}", e);
+        }
+    }
+
+    if let Some(smtp) = &settings.smtp {
+        let smtp = smtp.clone();
+        let subject = format!("Cashflow {}: {}", status, describe_alert(&alerts[0]));
+        let body = message.to_string();
+        let sent = tokio::task::spawn_blocking(move || send_mail(&smtp, &subject, body))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|r| r);
+        if let Err(e) = sent {
+            eprintln!("E-mail notification failed: {:#}", e);
+        }
+    }
+}
+
+/// Runs the command in a shell with the message on stdin and in the environment
+async fn run_command(command: &str, status: CheckStatus, message: &str) -> Result<()> {
+    #[cfg(unix)]
+    let mut cmd = tokio::process::Command::new("sh");
+    #[cfg(unix)]
+    cmd.arg("-c").arg(command);
+
+    #[cfg(windows)]
+    let mut cmd = tokio::process::Command::new("cmd");
+    #[cfg(windows)]
+    cmd.arg("/C").arg(command);
+
+    let mut child = cmd
+        .env("CASHFLOW_ALERT_STATUS", status.to_string())
+        .env("CASHFLOW_ALERT_MESSAGE", message)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run '{}'", command))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The command may not read stdin at all, so a closed pipe is fine
+        let _ = stdin.write_all(message.as_bytes()).await;
+    }
+
+    let exit = child.wait().await.context("Failed to wait for command")?;
+    if !exit.success() {
+        bail!("'{}' exited with {}", command, exit);
+    }
+    Ok(())
+}
+
+fn post_webhook(url: &str, body: &serde_json::Value) -> Result<()> {
+    ureq::AgentBuilder::new()
+        .timeout(NOTIFY_TIMEOUT)
+        .build()
+        .post(url)
+        .send_json(body)
+        .with_context(|| format!("Failed to POST to {}", url))?;
+    Ok(())
+}
+
+fn send_mail(smtp: &SmtpSettings, subject: &str, body: String) -> Result<()> {
+    use lettre::message::{Mailbox, header::ContentType};
+    use lettre::transport::smtp::authentication::Credentials;
+    use lettre::{Message, SmtpTransport, Transport};
+
+    let from: Mailbox = smtp
+        .from
+        .parse()
+        .with_context(|| format!("Invalid sender address '{}'", smtp.from))?;
+    let mut email = Message::builder()
+        .from(from)
+        .subject(subject)
+        .header(ContentType::TEXT_PLAIN);
+    for to in &smtp.to {
+        let to: Mailbox = to
+            .parse()
+            .with_context(|| format!("Invalid recipient address '{}'", to))?;
+        email = email.to(to);
+    }
+    let email = email.body(body).context("Failed to build e-mail")?;
+
+    let mut transport = match smtp.security {
+        SmtpSecurity::Tls => SmtpTransport::relay(&smtp.host)?,
+        SmtpSecurity::Starttls => SmtpTransport::starttls_relay(&smtp.host)?,
+        SmtpSecurity::None => SmtpTransport::builder_dangerous(&smtp.host),
+    }
+    .timeout(Some(NOTIFY_TIMEOUT));
+    if let Some(port) = smtp.port {
+        transport = transport.port(port);
+    }
+    if let (Some(username), Some(password)) = (&smtp.username, &smtp.password) {
+        transport = transport.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+
+    transport
+        .build()
+        .send(&email)
+        .with_context(|| format!("Failed to send e-mail via {}", smtp.host))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_format_alerts() {
+        let date = NaiveDate::from_ymd_opt(2025, 3, 5).unwrap();
+        let alerts = vec![
+            Alert::NegativeBalance {
+                date,
+                balance: Decimal::from(-1500),
+            },
+            Alert::StaleSnapshot {
+                date,
+                age_days: 30,
+                max_age_days: 14,
+            },
+        ];
+
+        assert_eq!(
+            format_alerts(&alerts),
+            "CRITICAL: balance goes negative on 05.03.2025 (-1 500 Kč)\n\
+             WARNING: balance snapshot from 05.03.2025 is 30 days old (limit 14)\n"
+        );
+    }
+}
@@ -1,30 +1,64 @@
 use crate::Result;
-use crate::config::{get_config_path, set_data_dir};
+use crate::commands::balance::parse_amount;
+use crate::config::{
+    ConfigSource, DEFAULT_PROFILE, DisplaySettings, Profile, add_profile, get_config_path,
+    load_config, locate_config, remove_profile, set_auto_prune_days, set_data_dir, use_profile,
+};
+use comfy_table::{Attribute, Cell, Table, presets::UTF8_FULL};
 use std::path::PathBuf;
 
 /// Executes config show command
 pub async fn execute_config_show() -> Result<()> {
+    // Load first, so legacy locations are migrated before reporting them
+    let config = load_config().await?;
     let actual_data_dir = crate::config::get_data_dir().await?;
+    let location = locate_config()?;
 
     println!("Configuration:");
     println!();
 
+    // Report which config file won
+    let source = match location.source {
+        ConfigSource::Xdg => "XDG",
+        ConfigSource::Legacy => "legacy",
+    };
+    if location.exists {
+        println!("Config file: {} ({})", location.path.display(), source);
+    } else {
+        println!(
+            "Config file: {} (not created yet, defaults in use)",
+            location.path.display()
+        );
+    }
+    if let Some(legacy) = &location.shadowed_legacy {
+        println!(
+            "  Note: Legacy config file {} also exists and is ignored",
+            legacy.display()
+        );
+    }
+
+    #[cfg(debug_assertions)]
+    println!("  Note: The config file is ignored in development builds");
+
+    println!(
+        "Profile: {}",
+        config
+            .active_profile_name()
+            .unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    );
+
     // Show if environment variable is overriding
     if let Ok(env_dir) = std::env::var("CASHFLOW_DATA_DIR") {
         println!("Data directory: {} (from CASHFLOW_DATA_DIR)", env_dir);
 
         #[cfg(not(debug_assertions))]
         {
-            use crate::config::load_config;
-
-            let config_path = get_config_path()?;
-            if config_path.exists() {
-                let config = load_config().await?;
+            if location.exists {
+                println!("  Note: Config file value is overridden");
                 println!(
-                    "  Note: Config file {} exists but is overridden",
-                    config_path.display()
+                    "        Config value: {}",
+                    config.active_data_dir()?.display()
                 );
-                println!("        Config value: {}", config.data_dir.display());
             }
         }
     } else {
@@ -34,20 +68,33 @@ pub async fn execute_config_show() -> Result<()> {
         #[cfg(debug_assertions)]
         {
             println!("  Source: Local development (./dev-data)");
-            println!("  Note: .cashflowrc is ignored in development builds");
         }
 
         #[cfg(not(debug_assertions))]
         {
-            let config_path = get_config_path()?;
-            if config_path.exists() {
-                println!("  Source: Config file ({})", config_path.display());
+            if location.exists {
+                println!("  Source: Config file");
             } else {
-                println!("  Source: Default (production mode → ~/.cashflow)");
+                println!("  Source: Default (production mode → $XDG_DATA_HOME/cashflow)");
             }
         }
     }
 
+    let notify = &config.notify;
+    let notifiers: Vec<&str> = [
+        notify.command.as_ref().map(|_| "command"),
+        notify.webhook.as_ref().map(|_| "webhook"),
+        notify.smtp.as_ref().map(|_| "e-mail"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if notifiers.is_empty() {
+        println!("Alert notifiers: none (add a [notify] section to the config file)");
+    } else {
+        println!("Alert notifiers: {}", notifiers.join(", "));
+    }
+
     println!();
     println!("💡 Tips:");
 
@@ -55,13 +102,11 @@ pub async fn execute_config_show() -> Result<()> {
     {
         println!("  • Development uses local ./dev-data/ (git-ignored)");
         println!("  • Override with: export CASHFLOW_DATA_DIR=/path/to/data");
-        println!("  • Your production .cashflowrc is ignored during development");
+        println!("  • Your production config file is ignored during development");
     }
 
     #[cfg(not(debug_assertions))]
     {
-        let config_path = get_config_path()?;
-        println!("  • Config file: {}", config_path.display());
         println!("  • Change directory: cashflow config set-data-dir <path>");
         println!("  • Override via env: export CASHFLOW_DATA_DIR=/path/to/data");
     }
@@ -71,30 +116,162 @@ pub async fn execute_config_show() -> Result<()> {
 
 /// Executes config set-data-dir command
 pub async fn execute_config_set_data_dir(path_str: &str) -> Result<()> {
+    let absolute_path = expand_path(path_str)?;
+
+    // Set the data directory
+    set_data_dir(absolute_path.clone()).await?;
+
+    println!("Data directory set to: {}", absolute_path.display());
+    println!();
+    println!("Configuration saved to: {}", get_config_path()?.display());
+
+    Ok(())
+}
+
+/// Expands ~ and converts the path to an absolute path
+fn expand_path(path_str: &str) -> Result<PathBuf> {
     // Parse and expand path
-    let path = if path_str.starts_with('~') {
+    let path = if let Some(rest) = path_str.strip_prefix('~') {
         // Expand ~ to home directory
         let home =
             dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Failed to get home directory"))?;
-        let path_without_tilde = path_str.strip_prefix("~/").unwrap_or(&path_str[1..]);
+        let path_without_tilde = rest.strip_prefix('/').unwrap_or(rest);
         home.join(path_without_tilde)
     } else {
         PathBuf::from(path_str)
     };
 
     // Convert to absolute path
-    let absolute_path = if path.is_absolute() {
-        path
+    if path.is_absolute() {
+        Ok(path)
     } else {
-        std::env::current_dir()?.join(path)
+        Ok(std::env::current_dir()?.join(path))
+    }
+}
+
+/// Executes config set-auto-prune command
+pub async fn execute_config_set_auto_prune(days_str: &str) -> Result<()> {
+    let days = match days_str {
+        "off" => None,
+        _ => Some(
+            days_str
+                .parse::<u32>()
+                .map_err(|_| anyhow::anyhow!("Invalid number of days. Use a number or 'off'"))?,
+        ),
     };
 
-    // Set the data directory
-    set_data_dir(absolute_path.clone()).await?;
+    set_auto_prune_days(days).await?;
 
-    println!("Data directory set to: {}", absolute_path.display());
+    match days {
+        Some(days) => println!(
+            "Past one-time transactions and snapshots older than {} days will be archived automatically.",
+            days
+        ),
+        None => println!("Automatic pruning disabled."),
+    }
     println!();
     println!("Configuration saved to: {}", get_config_path()?.display());
 
     Ok(())
 }
+
+/// Executes config profile add command
+pub async fn execute_config_profile_add(
+    name: &str,
+    data_dir: &str,
+    warning_threshold: Option<&str>,
+    show_past: bool,
+    stale_snapshot_days: Option<i64>,
+) -> Result<()> {
+    let mut display = DisplaySettings {
+        show_past,
+        ..DisplaySettings::default()
+    };
+    if let Some(threshold) = warning_threshold {
+        display.warning_threshold = parse_amount(threshold)?;
+    }
+    if let Some(days) = stale_snapshot_days {
+        display.stale_snapshot_days = days;
+    }
+
+    let profile = Profile {
+        data_dir: expand_path(data_dir)?,
+        display,
+    };
+    let data_dir = profile.data_dir.clone();
+
+    add_profile(name, profile).await?;
+
+    println!(
+        "Added profile '{}' (data directory: {})",
+        name,
+        data_dir.display()
+    );
+    println!("Use it with: cashflow --profile {} plan", name);
+    println!("Or make it active: cashflow config profile use {}", name);
+
+    Ok(())
+}
+
+/// Executes config profile list command
+pub async fn execute_config_profile_list() -> Result<()> {
+    let config = load_config().await?;
+    let active = config.active_profile_name();
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+
+    table.set_header(vec![
+        Cell::new("").add_attribute(Attribute::Bold),
+        Cell::new("Profile").add_attribute(Attribute::Bold),
+        Cell::new("Data directory").add_attribute(Attribute::Bold),
+        Cell::new("Warning threshold").add_attribute(Attribute::Bold),
+    ]);
+
+    let default_row = (
+        DEFAULT_PROFILE,
+        &config.data_dir,
+        &config.display,
+        active.is_none(),
+    );
+    let profile_rows = config.profiles.iter().map(|(name, profile)| {
+        (
+            name.as_str(),
+            &profile.data_dir,
+            &profile.display,
+            active.as_deref() == Some(name.as_str()),
+        )
+    });
+
+    for (name, data_dir, display, is_active) in std::iter::once(default_row).chain(profile_rows) {
+        table.add_row(vec![
+            Cell::new(if is_active { "*" } else { "" }),
+            Cell::new(name),
+            Cell::new(data_dir.display()),
+            Cell::new(crate::cli::format_amount(display.warning_threshold)),
+        ]);
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
+
+/// Executes config profile use command
+pub async fn execute_config_profile_use(name: &str) -> Result<()> {
+    use_profile(name).await?;
+
+    println!("Active profile: {}", name);
+
+    Ok(())
+}
+
+/// Executes config profile remove command
+pub async fn execute_config_profile_remove(name: &str) -> Result<()> {
+    let profile = remove_profile(name).await?;
+
+    println!("Removed profile '{}'", name);
+    println!("Data directory was kept: {}", profile.data_dir.display());
+
+    Ok(())
+}
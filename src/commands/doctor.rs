@@ -0,0 +1,112 @@
+use crate::Result;
+use crate::models::{ValidationIssue, auto_fix, validate_data};
+use crate::storage::{get_ron_path, load_data_unvalidated, save_data};
+use colored::*;
+use tokio::fs as async_fs;
+
+/// Executes doctor command: checks the data file for problems and optionally fixes them
+pub async fn execute_doctor(fix: bool) -> Result<()> {
+    let ron_path = get_ron_path().await?;
+
+    if !ron_path.exists() {
+        println!("No data file found at {}", ron_path.display());
+        return Ok(());
+    }
+
+    let mut data = load_data_unvalidated().await?;
+    let mut contents = async_fs::read_to_string(&ron_path).await?;
+    let file_name = ron_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| ron_path.display().to_string());
+
+    println!("Checking {}", ron_path.display());
+    println!();
+
+    let mut issues = validate_data(&data);
+
+    if fix && issues.iter().any(|i| i.is_fixable()) {
+        let fixes = auto_fix(&mut data);
+        save_data(&data).await?;
+        contents = async_fs::read_to_string(&ron_path).await?;
+
+        for description in &fixes {
+            println!("{} {}", "✓".green(), description);
+        }
+        println!();
+
+        issues = validate_data(&data);
+    }
+
+    if issues.is_empty() {
+        println!("{}", "No problems found.".green());
+        return Ok(());
+    }
+
+    for issue in &issues {
+        print_issue(issue, &file_name, &contents);
+    }
+
+    let fixable = issues.iter().filter(|i| i.is_fixable()).count();
+    println!(
+        "Found {} problem(s), {} can be fixed automatically.",
+        issues.len(),
+        fixable
+    );
+
+    if fixable > 0 {
+        println!("Run `cashflow doctor --fix` to fix them.");
+    }
+    if fixable < issues.len() {
+        println!("The remaining problems need to be fixed by editing the data file.");
+    }
+
+    Ok(())
+}
+
+/// Prints an issue with the lines of the data file that mention the affected entries
+fn print_issue(issue: &ValidationIssue, file_name: &str, contents: &str) {
+    let marker = if issue.is_fixable() {
+        "✗".yellow()
+    } else {
+        "✗".red()
+    };
+    println!("{} {}", marker, issue);
+
+    for id in issue.ids() {
+        for (line_number, line) in find_lines(contents, &id.to_string()) {
+            println!(
+                "    {}",
+                format!("{}:{}: {}", file_name, line_number, line.trim()).dimmed()
+            );
+        }
+    }
+
+    println!();
+}
+
+/// Finds all lines containing the needle, returns 1-based line numbers
+fn find_lines<'a>(contents: &'a str, needle: &str) -> Vec<(usize, &'a str)> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.contains(needle))
+        .map(|(index, line)| (index + 1, line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_lines() {
+        let contents = "(\n    id: \"abc\",\n    other: 1,\n    id: \"abc\",\n)";
+
+        let lines = find_lines(contents, "abc");
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].0, 2);
+        assert_eq!(lines[1].0, 4);
+    }
+}
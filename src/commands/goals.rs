@@ -0,0 +1,278 @@
+use crate::Result;
+use crate::cli::format_amount;
+use crate::commands::balance::{parse_amount, parse_date};
+use crate::commands::recurring::remap_overrides;
+use crate::models::{CashflowData, Goal, RecurringTransaction};
+use crate::projection::{DEFAULT_CONTRIBUTION_DAY, goal_progress};
+use crate::storage::{load_data, save_data};
+use anyhow::Context;
+use chrono::{Duration, Local};
+use comfy_table::{Attribute, Cell, CellAlignment, Color, Table, presets::UTF8_FULL};
+use rust_decimal::Decimal;
+
+/// Executes goals list command: progress and required monthly contribution of each goal
+pub async fn execute_goals_list() -> Result<()> {
+    let data = load_data().await?;
+
+    if data.goals.is_empty() {
+        println!("No goals found.");
+        println!("Add one with:");
+        println!(
+            "  cashflow goals add <name> --target <amount> --date <date> [--earmarked <amount>]"
+        );
+        return Ok(());
+    }
+
+    let today = Local::now().date_naive();
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+
+    table.set_header(vec![
+        Cell::new("Name").add_attribute(Attribute::Bold),
+        Cell::new("Target").add_attribute(Attribute::Bold),
+        Cell::new("By").add_attribute(Attribute::Bold),
+        Cell::new("Saved").add_attribute(Attribute::Bold),
+        Cell::new("Missing").add_attribute(Attribute::Bold),
+        Cell::new("Needed monthly").add_attribute(Attribute::Bold),
+        Cell::new("Transfer").add_attribute(Attribute::Bold),
+    ]);
+
+    for goal in &data.goals {
+        let transfer = contribution(&data, goal);
+        let day = transfer.map_or(DEFAULT_CONTRIBUTION_DAY, |t| t.day_of_month);
+        let progress = goal_progress(&data, goal, today, day);
+        let transfer_amount = transfer.map(|t| -t.amount_on(today));
+
+        let transfer_cell = match transfer_amount {
+            Some(amount) => Cell::new(format!("{} on day {}", format_amount(amount), day)).fg(
+                if amount >= progress.required_monthly {
+                    Color::Green
+                } else {
+                    Color::Yellow
+                },
+            ),
+            None => Cell::new("-"),
+        };
+
+        table.add_row(vec![
+            Cell::new(&goal.name),
+            Cell::new(format_amount(goal.target)).set_alignment(CellAlignment::Right),
+            Cell::new(goal.target_date.format("%d.%m.%Y").to_string()),
+            Cell::new(format_amount(progress.saved())).set_alignment(CellAlignment::Right),
+            Cell::new(format_amount(progress.missing)).set_alignment(CellAlignment::Right),
+            Cell::new(format_amount(progress.required_monthly)).set_alignment(CellAlignment::Right),
+            transfer_cell,
+        ]);
+    }
+
+    println!("{table}");
+
+    let earmarked: Decimal = data.goals.iter().map(|g| g.earmarked_on(today)).sum();
+    println!();
+    println!("Earmarked in the account: {}", format_amount(earmarked));
+    if data.goals.iter().any(|g| g.contribution_id.is_none()) {
+        println!(
+            "Needed monthly assumes a transfer on day {} until a transfer is set up with 'goals fund'",
+            DEFAULT_CONTRIBUTION_DAY
+        );
+    }
+
+    Ok(())
+}
+
+/// Executes goals add command
+pub async fn execute_goal_add(
+    name: &str,
+    target_str: &str,
+    date_str: &str,
+    earmarked_str: Option<&str>,
+) -> Result<()> {
+    let mut data = load_data().await?;
+    if data.goals.iter().any(|g| g.name == name) {
+        anyhow::bail!("Goal '{}' already exists", name);
+    }
+
+    let mut goal = Goal::new(
+        name.to_string(),
+        parse_amount(target_str)?.abs(),
+        parse_date(date_str)?,
+    );
+    if let Some(earmarked) = earmarked_str {
+        goal.earmarked = parse_amount(earmarked)?.abs();
+    }
+
+    data.goals.push(goal.clone());
+    save_data(&data).await?;
+
+    println!("Added goal:");
+    println!("  Name: {}", goal.name);
+    println!("  Target: {}", format_amount(goal.target));
+    println!("  By: {}", goal.target_date.format("%d.%m.%Y"));
+    println!("  Earmarked: {}", format_amount(goal.earmarked));
+    println!();
+    println!("Set up the monthly transfer with:");
+    println!("  cashflow goals fund {}", goal.name);
+
+    Ok(())
+}
+
+/// Executes goals edit command
+pub async fn execute_goal_edit(
+    name: &str,
+    target_str: Option<&str>,
+    date_str: Option<&str>,
+    earmarked_str: Option<&str>,
+) -> Result<()> {
+    let target = target_str.map(parse_amount).transpose()?;
+    let earmarked = earmarked_str.map(parse_amount).transpose()?;
+    let target_date = date_str.map(parse_date).transpose()?;
+
+    let mut data = load_data().await?;
+    let goal = find_goal_mut(&mut data, name)?;
+
+    // Summary lines are printed only once the edit is saved
+    let mut changes = Vec::new();
+
+    if let Some(target) = target {
+        goal.target = target.abs();
+        changes.push(format!("Updated target: {}", format_amount(goal.target)));
+    }
+    if let Some(earmarked) = earmarked {
+        goal.earmarked = earmarked.abs();
+        changes.push(format!(
+            "Updated earmarked: {}",
+            format_amount(goal.earmarked)
+        ));
+    }
+
+    if let Some(target_date) = target_date {
+        goal.target_date = target_date;
+        changes.push(format!(
+            "Updated target date: {}",
+            target_date.format("%d.%m.%Y")
+        ));
+
+        // The transfer stops at the new target date
+        if let Some(id) = goal.contribution_id
+            && let Some(transfer) = data.recurring.iter_mut().find(|r| r.id == id)
+        {
+            transfer.end_date = Some(target_date);
+        }
+    }
+
+    save_data(&data).await?;
+
+    for change in &changes {
+        println!("{}", change);
+    }
+    println!(
+        "Goal updated. Run 'goals fund {}' to adjust the transfer.",
+        name
+    );
+
+    Ok(())
+}
+
+/// Executes goals fund command: creates or adjusts the recurring transfer so the goal is
+/// reached by its target date
+pub async fn execute_goal_fund(name: &str, day: Option<u8>) -> Result<()> {
+    let mut data = load_data().await?;
+    let today = Local::now().date_naive();
+
+    let goal = find_goal_mut(&mut data, name)?.clone();
+    let transfer = contribution(&data, &goal);
+    let day = day
+        .or(transfer.map(|t| t.day_of_month))
+        .unwrap_or(DEFAULT_CONTRIBUTION_DAY);
+
+    let progress = goal_progress(&data, &goal, today, day);
+    if progress.missing.is_zero() {
+        println!("Goal '{}' is already fully funded.", name);
+        return Ok(());
+    }
+    if progress.contributions_left == 0 {
+        anyhow::bail!(
+            "No transfer on day {} fits before {}. Choose another day or a later target date.",
+            day,
+            goal.target_date.format("%d.%m.%Y")
+        );
+    }
+
+    let amount = -progress.required_monthly;
+    match goal
+        .contribution_id
+        .and_then(|id| data.recurring.iter_mut().find(|r| r.id == id))
+    {
+        Some(transfer) => {
+            // Transfers up to today are counted as contributed and keep their amount
+            transfer.schedule_amount(today + Duration::days(1), amount);
+            if transfer.day_of_month != day {
+                transfer.day_of_month = day;
+                remap_overrides(transfer);
+            }
+            transfer.end_date = Some(goal.target_date);
+            transfer.active = true;
+        }
+        None => {
+            let mut transfer =
+                RecurringTransaction::new(format!("Savings: {}", goal.name), amount, day);
+            transfer.end_date = Some(goal.target_date);
+            transfer.validate()?;
+
+            find_goal_mut(&mut data, name)?.contribution_id = Some(transfer.id);
+            data.recurring.push(transfer);
+        }
+    }
+
+    save_data(&data).await?;
+
+    println!(
+        "Transfer for '{}': {} on day {} of each month until {} ({} transfers)",
+        name,
+        format_amount(progress.required_monthly),
+        day,
+        goal.target_date.format("%d.%m.%Y"),
+        progress.contributions_left
+    );
+
+    Ok(())
+}
+
+/// Executes goals delete command; the goal's recurring transfer is deleted too
+pub async fn execute_goal_delete(name: &str) -> Result<()> {
+    let mut data = load_data().await?;
+
+    let index = data
+        .goals
+        .iter()
+        .position(|g| g.name == name)
+        .with_context(|| format!("Goal '{}' not found", name))?;
+    let goal = data.goals.remove(index);
+
+    if let Some(id) = goal.contribution_id {
+        data.recurring.retain(|r| r.id != id);
+    }
+
+    save_data(&data).await?;
+
+    println!("Deleted goal: {}", goal.name);
+    if goal.contribution_id.is_some() {
+        println!("Deleted its recurring transfer as well.");
+    }
+
+    Ok(())
+}
+
+fn find_goal_mut<'a>(data: &'a mut CashflowData, name: &str) -> Result<&'a mut Goal> {
+    data.goals
+        .iter_mut()
+        .find(|g| g.name == name)
+        .with_context(|| format!("Goal '{}' not found. Use 'goals' to see all goals.", name))
+}
+
+/// Recurring transfer funding the goal, if it still exists
+fn contribution<'a>(data: &'a CashflowData, goal: &Goal) -> Option<&'a RecurringTransaction> {
+    goal.contribution_id
+        .and_then(|id| data.recurring.iter().find(|r| r.id == id))
+}
@@ -0,0 +1,141 @@
+use crate::Result;
+use crate::cli::format_amount;
+use crate::commands::balance::{parse_amount, parse_date};
+use crate::models::{InstallmentPlan, RecurringTransaction};
+use crate::storage::{load_data, save_data};
+use chrono::{Datelike, Local, Months};
+use comfy_table::{Attribute, Cell, CellAlignment, Table, presets::UTF8_FULL};
+use rust_decimal::Decimal;
+
+/// Executes loan add command: creates a recurring transaction with an installment plan
+pub async fn execute_loan_add(
+    description: &str,
+    principal_str: Option<&str>,
+    rate_str: &str,
+    installments: u32,
+    amount_str: Option<&str>,
+    day: Option<u8>,
+    first_str: Option<&str>,
+) -> Result<()> {
+    let principal = principal_str
+        .map(parse_amount)
+        .transpose()?
+        .map(|p| p.abs());
+    let annual_rate = parse_amount(rate_str)?;
+    InstallmentPlan::check_terms(annual_rate, installments)?;
+
+    // Installments are always expenses, whatever sign was typed
+    let payment = match (amount_str, principal) {
+        (Some(amount_str), _) => parse_amount(amount_str)?.abs(),
+        (None, Some(principal)) => {
+            InstallmentPlan::annuity_payment(principal, annual_rate, installments)?
+        }
+        (None, None) => anyhow::bail!("Specify the principal or the installment amount"),
+    };
+
+    // Only the month of the first installment matters for the schedule
+    let today = Local::now().date_naive();
+    let (day, first_payment) = match first_str {
+        Some(first_str) => {
+            let first = parse_date(first_str)?;
+            (day.unwrap_or(first.day() as u8), first)
+        }
+        None => {
+            let day = day.unwrap_or(today.day() as u8);
+            let first = if day as u32 >= today.day() {
+                today
+            } else {
+                today + Months::new(1)
+            };
+            (day, first)
+        }
+    };
+
+    let mut transaction = RecurringTransaction::new(description.to_string(), -payment, day);
+    transaction.installment_plan = Some(InstallmentPlan {
+        principal,
+        annual_rate,
+        installments,
+        first_payment,
+    });
+    transaction.validate()?;
+
+    let mut data = load_data().await?;
+    data.recurring.push(transaction.clone());
+    save_data(&data).await?;
+
+    println!("Added loan:");
+    println!("  Description: {}", transaction.description);
+    println!("  Installment: {}", format_amount(transaction.amount));
+    println!("  Installments: {}", installments);
+    if let Some(plan) = &transaction.installment_plan {
+        println!("  Paid off: {}", plan.last_payment(day).format("%d.%m.%Y"));
+    }
+    println!("  ID: {}", transaction.id);
+
+    Ok(())
+}
+
+/// Executes loan list command
+pub async fn execute_loan_list() -> Result<()> {
+    let data = load_data().await?;
+    let today = Local::now().date_naive();
+
+    let loans: Vec<_> = data
+        .recurring
+        .iter()
+        .filter_map(|txn| txn.installment_plan.as_ref().map(|plan| (txn, plan)))
+        .collect();
+
+    if loans.is_empty() {
+        println!("No loans found.");
+        println!("Add one with:");
+        println!(
+            "  cashflow loan add -d <description> --principal <amount> --rate <percent> -n <installments> --day <day>"
+        );
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+
+    table.set_header(vec![
+        Cell::new("ID").add_attribute(Attribute::Bold),
+        Cell::new("Description").add_attribute(Attribute::Bold),
+        Cell::new("Installment").add_attribute(Attribute::Bold),
+        Cell::new("Paid").add_attribute(Attribute::Bold),
+        Cell::new("Remaining principal").add_attribute(Attribute::Bold),
+        Cell::new("Paid off").add_attribute(Attribute::Bold),
+    ]);
+
+    let mut total_remaining = Decimal::ZERO;
+
+    for (txn, plan) in &loans {
+        let id_short = txn.id.to_string().chars().take(8).collect::<String>();
+        let paid = plan.paid_before(today, txn.day_of_month);
+        let remaining = plan.remaining_principal(-txn.amount_on(today), paid);
+        total_remaining += remaining;
+
+        table.add_row(vec![
+            Cell::new(id_short),
+            Cell::new(&txn.description),
+            Cell::new(format_amount(txn.amount_on(today))).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{}/{}", paid, plan.installments)),
+            Cell::new(format_amount(remaining)).set_alignment(CellAlignment::Right),
+            Cell::new(
+                plan.last_payment(txn.day_of_month)
+                    .format("%d.%m.%Y")
+                    .to_string(),
+            ),
+        ]);
+    }
+
+    println!("{table}");
+    println!();
+    println!(
+        "Total remaining principal: {}",
+        format_amount(total_remaining)
+    );
+
+    Ok(())
+}
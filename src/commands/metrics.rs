@@ -0,0 +1,23 @@
+use crate::Result;
+use crate::config::get_display_settings;
+use crate::projection::{CashflowMetrics, project_cashflow};
+use crate::storage::load_data;
+
+/// Executes metrics command: prints the metrics in the Prometheus text format
+/// Suitable for the node_exporter textfile collector
+pub async fn execute_metrics(days: i64) -> Result<()> {
+    print!("{}", collect_metrics(days).await?);
+    Ok(())
+}
+
+/// Projects the next `days` days and renders the metrics in the Prometheus text format
+/// Shared by `cashflow metrics` and the `/metrics` endpoint of `cashflow serve`
+pub async fn collect_metrics(days: i64) -> Result<String> {
+    let data = load_data().await?;
+    let warning_threshold = get_display_settings().await?.warning_threshold;
+
+    let projection = project_cashflow(&data, days)?;
+    let metrics = CashflowMetrics::new(&data, &projection, days, warning_threshold);
+
+    Ok(metrics.to_prometheus())
+}
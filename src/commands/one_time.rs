@@ -1,11 +1,13 @@
 use crate::Result;
 use crate::cli::format_amount;
-use crate::commands::balance::{parse_amount, parse_date};
+use crate::commands::balance::{parse_amount, parse_category, parse_date};
+use crate::commands::resolve::{IdCandidate, resolve_id};
 use crate::models::OneTimeTransaction;
-use crate::storage::{load_data, save_data};
+use crate::storage::{load_archive, load_data, save_data};
 use anyhow::Context;
 use chrono::Local;
 use comfy_table::{Attribute, Cell, Color, Table, presets::UTF8_FULL};
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
 /// Executes add command (one-time transaction)
@@ -13,6 +15,7 @@ pub async fn execute_one_time_add(
     description: &str,
     amount_str: &str,
     date_str: &str,
+    category: Option<&str>,
 ) -> Result<()> {
     // Parse amount and date
     let amount = parse_amount(amount_str)?;
@@ -22,7 +25,9 @@ pub async fn execute_one_time_add(
     let mut data = load_data().await?;
 
     // Create one-time transaction
-    let transaction = OneTimeTransaction::new(description.to_string(), amount, date);
+    let mut transaction = OneTimeTransaction::new(description.to_string(), amount, date);
+    transaction.category = parse_category(category);
+    transaction.validate()?;
 
     data.one_time.push(transaction.clone());
 
@@ -33,16 +38,34 @@ pub async fn execute_one_time_add(
     println!("  Description: {}", transaction.description);
     println!("  Amount: {}", format_amount(transaction.amount));
     println!("  Date: {}", transaction.date.format("%d.%m.%Y"));
+    if let Some(category) = &transaction.category {
+        println!("  Category: {}", category);
+    }
     println!("  ID: {}", transaction.id);
 
     Ok(())
 }
 
 /// Executes list command (one-time transactions)
-pub async fn execute_one_time_list(upcoming: bool) -> Result<()> {
+/// With `archived`, pruned transactions from the archive are listed as well
+pub async fn execute_one_time_list(upcoming: bool, archived: bool) -> Result<()> {
     let data = load_data().await?;
 
+    // Numbers refer to the full list of stored transactions, archived ones have none
+    let numbers: HashMap<Uuid, usize> = list_order(&data.one_time)
+        .iter()
+        .enumerate()
+        .map(|(index, t)| (t.id, index + 1))
+        .collect();
+
     let mut transactions = data.one_time.clone();
+    let mut archived_ids = HashSet::new();
+
+    if archived {
+        let archive = load_archive().await?;
+        archived_ids.extend(archive.one_time.iter().map(|t| t.id));
+        transactions.extend(archive.one_time);
+    }
 
     // Filter upcoming if requested
     if upcoming {
@@ -68,8 +91,10 @@ pub async fn execute_one_time_list(upcoming: bool) -> Result<()> {
     table.load_preset(UTF8_FULL);
 
     table.set_header(vec![
+        Cell::new("#").add_attribute(Attribute::Bold),
         Cell::new("ID").add_attribute(Attribute::Bold),
         Cell::new("Description").add_attribute(Attribute::Bold),
+        Cell::new("Category").add_attribute(Attribute::Bold),
         Cell::new("Amount").add_attribute(Attribute::Bold),
         Cell::new("Date").add_attribute(Attribute::Bold),
     ]);
@@ -82,9 +107,21 @@ pub async fn execute_one_time_list(upcoming: bool) -> Result<()> {
             Color::Green
         };
 
+        let mut description = txn.description.clone();
+        if archived_ids.contains(&txn.id) {
+            description.push_str(" (archived)");
+        }
+
         table.add_row(vec![
+            Cell::new(
+                numbers
+                    .get(&txn.id)
+                    .map(|n| n.to_string())
+                    .unwrap_or_default(),
+            ),
             Cell::new(id_short),
-            Cell::new(&txn.description),
+            Cell::new(description),
+            Cell::new(txn.category.as_deref().unwrap_or("")),
             Cell::new(format_amount(txn.amount)).fg(amount_color),
             Cell::new(txn.date.format("%d.%m.%Y").to_string()),
         ]);
@@ -103,9 +140,10 @@ pub async fn execute_one_time_edit(
     amount: Option<&str>,
     date: Option<&str>,
     description: Option<&str>,
+    category: Option<&str>,
 ) -> Result<()> {
     let mut data = load_data().await?;
-    let id = parse_uuid(id_str, &data.one_time)?;
+    let id = resolve_one_time_id(id_str, &data.one_time)?;
 
     let transaction = data
         .one_time
@@ -113,22 +151,44 @@ pub async fn execute_one_time_edit(
         .find(|t| t.id == id)
         .context("One-time transaction not found")?;
 
+    // Summary lines are printed only once the edit is valid and saved
+    let mut changes = Vec::new();
+
     if let Some(amount_str) = amount {
         transaction.amount = parse_amount(amount_str)?;
-        println!("Updated amount: {}", format_amount(transaction.amount));
+        changes.push(format!(
+            "Updated amount: {}",
+            format_amount(transaction.amount)
+        ));
     }
 
     if let Some(date_str) = date {
         transaction.date = parse_date(date_str)?;
-        println!("Updated date: {}", transaction.date.format("%d.%m.%Y"));
+        changes.push(format!(
+            "Updated date: {}",
+            transaction.date.format("%d.%m.%Y")
+        ));
     }
 
     if let Some(desc) = description {
         transaction.description = desc.to_string();
-        println!("Updated description: {}", desc);
+        changes.push(format!("Updated description: {}", desc));
+    }
+
+    if let Some(category) = category {
+        transaction.category = parse_category(Some(category));
+        changes.push(match &transaction.category {
+            Some(category) => format!("Updated category: {}", category),
+            None => "Removed category".to_string(),
+        });
     }
 
+    transaction.validate()?;
     save_data(&data).await?;
+
+    for change in &changes {
+        println!("{}", change);
+    }
     println!("One-time transaction updated successfully.");
 
     Ok(())
@@ -137,7 +197,7 @@ pub async fn execute_one_time_edit(
 /// Executes one-time delete command
 pub async fn execute_one_time_delete(id_str: &str) -> Result<()> {
     let mut data = load_data().await?;
-    let id = parse_uuid(id_str, &data.one_time)?;
+    let id = resolve_one_time_id(id_str, &data.one_time)?;
 
     let index = data
         .one_time
@@ -153,44 +213,20 @@ pub async fn execute_one_time_delete(id_str: &str) -> Result<()> {
     Ok(())
 }
 
-/// Parses UUID from string, supports both full and short (8 char) format
-fn parse_uuid(s: &str, transactions: &[OneTimeTransaction]) -> Result<Uuid> {
-    // Try full UUID first
-    if let Ok(uuid) = Uuid::parse_str(s) {
-        return Ok(uuid);
-    }
-
-    // Try short format (8 chars) - match against existing UUIDs by prefix
-    if s.len() >= 8 {
-        let prefix = s.to_lowercase();
-        let matching: Vec<_> = transactions
-            .iter()
-            .filter(|t| {
-                let uuid_str = t.id.to_string().to_lowercase();
-                uuid_str.starts_with(&prefix)
-            })
-            .collect();
-
-        match matching.len() {
-            0 => {
-                anyhow::bail!(
-                    "No one-time transaction found with ID starting with '{}'. Use 'one-time list' to see available IDs.",
-                    s
-                )
-            }
-            1 => return Ok(matching[0].id),
-            _ => {
-                anyhow::bail!(
-                    "Multiple transactions match '{}'. Use full UUID to be more specific. Use 'one-time list' to see all IDs.",
-                    s
-                )
-            }
-        }
-    }
+/// One-time transactions in the order `one-time list` numbers them
+fn list_order(transactions: &[OneTimeTransaction]) -> Vec<&OneTimeTransaction> {
+    let mut ordered: Vec<_> = transactions.iter().collect();
+    ordered.sort_by_key(|t| t.date);
+    ordered
+}
 
-    anyhow::bail!(
-        "Invalid UUID format. Use full UUID or short format (first 8 characters) from 'one-time list'"
-    )
+/// Finds a one-time transaction by ID, ID prefix, list number or description
+fn resolve_one_time_id(s: &str, transactions: &[OneTimeTransaction]) -> Result<Uuid> {
+    let candidates: Vec<_> = list_order(transactions)
+        .into_iter()
+        .map(IdCandidate::one_time)
+        .collect();
+    resolve_id(s, &candidates, "one-time transaction", "one-time list")
 }
 
 /// Executes export command
@@ -206,10 +242,14 @@ pub async fn execute_export(format: &str) -> Result<()> {
             // Simple CSV export of all transactions
             println!("Type,Description,Amount,Date/Day,Active");
 
+            let today = Local::now().date_naive();
             for txn in &data.recurring {
                 println!(
                     "recurring,\"{}\",{},{},{}",
-                    txn.description, txn.amount, txn.day_of_month, txn.active
+                    txn.description,
+                    txn.amount_on(today),
+                    txn.day_of_month,
+                    txn.active
                 );
             }
 
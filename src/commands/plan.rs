@@ -1,27 +1,54 @@
 use crate::Result;
-use crate::cli::{PlanDisplayOptions, format_amount, print_plan_table};
-use crate::projection::project_cashflow;
-use crate::storage::load_data;
-use rust_decimal::Decimal;
-use std::str::FromStr;
+use crate::cli::{
+    PlanDisplayOptions, format_amount, print_plan_table, print_scenario_comparison,
+    print_stale_snapshot_warning,
+};
+use crate::config::get_display_settings;
+use crate::projection::{budget_overruns, project_cashflow};
+use crate::storage::load_data_with_archive;
+use chrono::Duration;
 
 /// Executes the plan command: shows cashflow projection for N days
-pub async fn execute_plan(days: i64, show_past: bool) -> Result<()> {
-    // Load data from RON file
-    let data = load_data().await?;
+/// With a scenario, the scenario's plan is shown followed by a comparison with the base plan
+pub async fn execute_plan(days: i64, show_past: bool, scenario: Option<&str>) -> Result<()> {
+    // Archived entries predate the latest snapshot, so they don't change the projection,
+    // but they still count towards the budgets of the current month
+    let base_data = load_data_with_archive().await?;
 
     // Check if we have a balance snapshot
-    if data.balance_snapshots.is_empty() {
+    if base_data.balance_snapshots.is_empty() {
         anyhow::bail!(
             "No balance snapshot found. Please set your current balance first:\n  \
              cashflow balance set <amount>"
         );
     }
 
+    // Apply the scenario overlay, if requested
+    let data = match scenario {
+        Some(name) => base_data
+            .scenarios
+            .iter()
+            .find(|s| s.name == name)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Scenario '{}' not found. Use 'scenario list' to see available scenarios.",
+                    name
+                )
+            })?
+            .apply(&base_data),
+        None => base_data.clone(),
+    };
+
     // Generate projection
     let projection = project_cashflow(&data, days)?;
 
-    if projection.future_txns.is_empty() {
+    // Warning threshold and defaults come from the active profile
+    let display = get_display_settings().await?;
+
+    if projection.future_txns.is_empty() && scenario.is_none() {
+        if projection.is_snapshot_stale(display.stale_snapshot_days) {
+            print_stale_snapshot_warning(&projection);
+        }
         println!("No transactions scheduled for the next {} days.", days);
         println!(
             "Current balance: {}",
@@ -30,15 +57,30 @@ pub async fn execute_plan(days: i64, show_past: bool) -> Result<()> {
         return Ok(());
     }
 
-    // Warning threshold: 10,000 Kč
-    let warning_threshold = Decimal::from_str("10000").unwrap();
-
     // Print the table with today's date and calculated balance
     let display_options = PlanDisplayOptions {
-        warning_threshold,
-        show_past,
+        warning_threshold: display.warning_threshold,
+        show_past: show_past || display.show_past,
+        stale_snapshot_days: display.stale_snapshot_days,
+        // Covers the past rows too, in case they are shown
+        over_budget: budget_overruns(
+            &data,
+            projection.balance_snapshot.date,
+            projection.start_date + Duration::days(days),
+            projection.start_date,
+        ),
+        goals: data.goals.clone(),
     };
+    if let Some(name) = scenario {
+        println!("Scénář: {}", name);
+    }
     print_plan_table(&projection, &display_options);
 
+    if let Some(name) = scenario {
+        let base_projection = project_cashflow(&base_data, days)?;
+        println!();
+        print_scenario_comparison(&base_projection, &projection, name);
+    }
+
     Ok(())
 }
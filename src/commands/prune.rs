@@ -0,0 +1,53 @@
+use crate::Result;
+use crate::commands::balance::parse_date;
+use crate::storage::{
+    get_archive_path, load_archive, load_data, prune_data, save_archive, save_data,
+};
+
+/// Executes prune command: moves past one-time transactions and superseded
+/// balance snapshots into the archive file
+pub async fn execute_prune(before_str: Option<&str>, dry_run: bool) -> Result<()> {
+    let mut data = load_data().await?;
+
+    let Some(latest_snapshot) = data.balance_snapshots.iter().map(|s| s.date).max() else {
+        anyhow::bail!(
+            "No balance snapshot found. Nothing can be pruned before the first snapshot:\n  \
+             cashflow balance set <amount>"
+        );
+    };
+
+    // Default to the latest snapshot, everything before it is already reflected in the balance
+    let before = match before_str {
+        Some(date_str) => parse_date(date_str)?,
+        None => latest_snapshot,
+    };
+
+    if before > latest_snapshot {
+        println!(
+            "Note: only entries before the latest balance snapshot ({}) can be pruned.",
+            latest_snapshot.format("%d.%m.%Y")
+        );
+    }
+
+    let mut archive = load_archive().await?;
+    let summary = prune_data(&mut data, &mut archive, before);
+
+    if summary.is_empty() {
+        println!("Nothing to prune.");
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("Would archive:");
+    } else {
+        // Write the archive first so nothing is lost if saving data fails
+        save_archive(&archive).await?;
+        save_data(&data).await?;
+        println!("Archived to {}:", get_archive_path().await?.display());
+    }
+
+    println!("  One-time transactions: {}", summary.one_time);
+    println!("  Balance snapshots: {}", summary.balance_snapshots);
+
+    Ok(())
+}
@@ -1,27 +1,37 @@
 use crate::Result;
 use crate::cli::format_amount;
-use crate::commands::balance::parse_amount;
-use crate::models::RecurringTransaction;
+use crate::commands::balance::{parse_amount, parse_category, parse_date};
+use crate::commands::resolve::{IdCandidate, resolve_id};
+use crate::models::{AmountRange, OccurrenceOverride, OccurrenceStatus, RecurringTransaction};
+use crate::projection::{get_transaction_date_in_month, is_scheduled_on, scheduled_dates};
 use crate::storage::{load_data, save_data};
-use anyhow::Context;
+use anyhow::{Context, bail};
+use chrono::{Duration, Local, NaiveDate};
 use comfy_table::{Attribute, Cell, Color, Table, presets::UTF8_FULL};
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
 /// Executes recurring add command
-pub async fn execute_recurring_add(description: &str, amount_str: &str, day: u8) -> Result<()> {
-    // Validate day
-    if !(1..=31).contains(&day) {
-        anyhow::bail!("Day must be between 1 and 31");
-    }
-
+pub async fn execute_recurring_add(
+    description: &str,
+    amount_str: &str,
+    day: u8,
+    min: Option<&str>,
+    max: Option<&str>,
+    category: Option<&str>,
+) -> Result<()> {
     // Parse amount
     let amount = parse_amount(amount_str)?;
+    let amount_range = parse_range(min, max, amount)?;
 
     // Load data
     let mut data = load_data().await?;
 
     // Create recurring transaction
-    let transaction = RecurringTransaction::new(description.to_string(), amount, day);
+    let mut transaction = RecurringTransaction::new(description.to_string(), amount, day);
+    transaction.amount_range = amount_range;
+    transaction.category = parse_category(category);
+    transaction.validate()?;
 
     data.recurring.push(transaction.clone());
 
@@ -31,7 +41,13 @@ pub async fn execute_recurring_add(description: &str, amount_str: &str, day: u8)
     println!("Added recurring transaction:");
     println!("  Description: {}", transaction.description);
     println!("  Amount: {}", format_amount(transaction.amount));
+    if let Some(range) = &transaction.amount_range {
+        println!("  Range: {}", format_range(range));
+    }
     println!("  Day of month: {}", transaction.day_of_month);
+    if let Some(category) = &transaction.category {
+        println!("  Category: {}", category);
+    }
     println!("  ID: {}", transaction.id);
 
     Ok(())
@@ -48,34 +64,56 @@ pub async fn execute_recurring_list() -> Result<()> {
         return Ok(());
     }
 
+    let today = Local::now().date_naive();
+
     // Sort by day of month
-    let mut transactions = data.recurring.clone();
-    transactions.sort_by_key(|t| t.day_of_month);
+    let transactions = list_order(&data.recurring);
 
     let mut table = Table::new();
     table.load_preset(UTF8_FULL);
 
     table.set_header(vec![
+        Cell::new("#").add_attribute(Attribute::Bold),
         Cell::new("ID").add_attribute(Attribute::Bold),
         Cell::new("Description").add_attribute(Attribute::Bold),
+        Cell::new("Category").add_attribute(Attribute::Bold),
         Cell::new("Amount").add_attribute(Attribute::Bold),
         Cell::new("Day").add_attribute(Attribute::Bold),
+        Cell::new("Next change").add_attribute(Attribute::Bold),
         Cell::new("Active").add_attribute(Attribute::Bold),
     ]);
 
-    for txn in &transactions {
+    for (index, txn) in transactions.iter().enumerate() {
         let id_short = txn.id.to_string().chars().take(8).collect::<String>();
-        let amount_color = if txn.amount.is_sign_negative() {
+        let amount = txn.amount_on(today);
+        let amount_color = if amount.is_sign_negative() {
             Color::Red
         } else {
             Color::Green
         };
 
         table.add_row(vec![
+            Cell::new(index + 1),
             Cell::new(id_short),
             Cell::new(&txn.description),
-            Cell::new(format_amount(txn.amount)).fg(amount_color),
+            Cell::new(txn.category.as_deref().unwrap_or("")),
+            Cell::new(match &txn.range_on(today) {
+                Some(range) => format!("{} ({})", format_amount(amount), format_range(range)),
+                None => format_amount(amount),
+            })
+            .fg(amount_color),
             Cell::new(txn.day_of_month.to_string()),
+            Cell::new(
+                txn.next_amount_change(today)
+                    .map(|c| {
+                        format!(
+                            "{} from {}",
+                            format_amount(c.amount),
+                            c.effective_from.format("%d.%m.%Y")
+                        )
+                    })
+                    .unwrap_or_default(),
+            ),
             Cell::new(if txn.active { "✓" } else { "✗" }).fg(if txn.active {
                 Color::Green
             } else {
@@ -94,7 +132,7 @@ pub async fn execute_recurring_list() -> Result<()> {
 /// Executes recurring disable command
 pub async fn execute_recurring_disable(id_str: &str) -> Result<()> {
     let mut data = load_data().await?;
-    let id = parse_uuid(id_str, &data.recurring)?;
+    let id = resolve_recurring_id(id_str, &data.recurring)?;
 
     let transaction = data
         .recurring
@@ -115,7 +153,7 @@ pub async fn execute_recurring_disable(id_str: &str) -> Result<()> {
 /// Executes recurring enable command
 pub async fn execute_recurring_enable(id_str: &str) -> Result<()> {
     let mut data = load_data().await?;
-    let id = parse_uuid(id_str, &data.recurring)?;
+    let id = resolve_recurring_id(id_str, &data.recurring)?;
 
     let transaction = data
         .recurring
@@ -136,7 +174,7 @@ pub async fn execute_recurring_enable(id_str: &str) -> Result<()> {
 /// Executes recurring delete command
 pub async fn execute_recurring_delete(id_str: &str) -> Result<()> {
     let mut data = load_data().await?;
-    let id = parse_uuid(id_str, &data.recurring)?;
+    let id = resolve_recurring_id(id_str, &data.recurring)?;
 
     let index = data
         .recurring
@@ -152,15 +190,177 @@ pub async fn execute_recurring_delete(id_str: &str) -> Result<()> {
     Ok(())
 }
 
+/// Changes requested by `recurring edit`; fields left as None are kept
+#[derive(Debug, Default)]
+pub struct RecurringEdit<'a> {
+    pub amount: Option<&'a str>,
+    /// Date the new amount takes effect (default today)
+    pub effective: Option<&'a str>,
+    /// Replace the amount for past occurrences too
+    pub rewrite: bool,
+    pub day: Option<u8>,
+    pub description: Option<&'a str>,
+    pub min: Option<&'a str>,
+    pub max: Option<&'a str>,
+    /// Drop the amount range
+    pub no_range: bool,
+    /// New category, empty to remove
+    pub category: Option<&'a str>,
+}
+
+/// Typed changes to a recurring transaction, shared by `recurring edit` and
+/// `PATCH /api/recurring/{id}`; fields left as None are kept
+#[derive(Debug, Default)]
+pub struct RecurringChanges {
+    pub amount: Option<Decimal>,
+    /// Date the new amount takes effect (default today)
+    pub effective_from: Option<NaiveDate>,
+    /// Replace the amount for past occurrences too
+    pub rewrite: bool,
+    /// New day of month, overrides move along to the new date
+    pub day_of_month: Option<u8>,
+    pub description: Option<String>,
+    pub min: Option<Decimal>,
+    pub max: Option<Decimal>,
+    /// Drop the amount range
+    pub no_range: bool,
+    /// New category, empty to remove
+    pub category: Option<String>,
+}
+
+/// Applies the changes to a recurring transaction and returns a summary line per change
+/// A new amount is added to the amount history from the effective date (default today),
+/// so past occurrences keep their old amount unless `rewrite` is set
+pub fn apply_recurring_changes(
+    transaction: &mut RecurringTransaction,
+    changes: RecurringChanges,
+    today: NaiveDate,
+) -> Result<Vec<String>> {
+    let mut summary = Vec::new();
+
+    // A new range belongs to the amount in effect today, or to a newly scheduled amount
+    let mut range_date = today;
+
+    if let Some(new_amount) = changes.amount {
+        if changes.rewrite {
+            transaction.set_amount(new_amount);
+            summary.push(format!("Updated amount: {}", format_amount(new_amount)));
+        } else {
+            let effective_from = changes.effective_from.unwrap_or(today);
+            transaction.schedule_amount(effective_from, new_amount);
+            range_date = effective_from;
+            summary.push(format!(
+                "Updated amount: {} from {}",
+                format_amount(new_amount),
+                effective_from.format("%d.%m.%Y")
+            ));
+        }
+    }
+
+    if let Some(day) = changes.day_of_month {
+        transaction.day_of_month = day;
+        remap_overrides(transaction);
+        summary.push(format!("Updated day of month: {}", day));
+    }
+
+    if let Some(description) = changes.description {
+        summary.push(format!("Updated description: {}", description));
+        transaction.description = description;
+    }
+
+    if let Some(range) = check_range(changes.min, changes.max, transaction.amount_on(range_date))? {
+        summary.push(format!("Updated range: {}", format_range(&range)));
+        transaction.set_range_on(range_date, range);
+    } else if changes.no_range {
+        transaction.amount_range = None;
+        summary.push("Removed amount range".to_string());
+    }
+
+    if let Some(category) = changes.category {
+        transaction.category = parse_category(Some(&category));
+        summary.push(match &transaction.category {
+            Some(category) => format!("Updated category: {}", category),
+            None => "Removed category".to_string(),
+        });
+    }
+
+    Ok(summary)
+}
+
 /// Executes recurring edit command
-pub async fn execute_recurring_edit(
+pub async fn execute_recurring_edit(id_str: &str, edit: &RecurringEdit<'_>) -> Result<()> {
+    let RecurringEdit {
+        amount,
+        effective,
+        rewrite,
+        day,
+        description,
+        min,
+        max,
+        no_range,
+        category,
+    } = *edit;
+
+    let changes = RecurringChanges {
+        amount: amount.map(parse_amount).transpose()?,
+        effective_from: effective.map(parse_date).transpose()?,
+        rewrite,
+        day_of_month: day,
+        description: description.map(str::to_string),
+        min: min.map(parse_amount).transpose()?,
+        max: max.map(parse_amount).transpose()?,
+        no_range,
+        category: category.map(str::to_string),
+    };
+
+    let mut data = load_data().await?;
+    let id = resolve_recurring_id(id_str, &data.recurring)?;
+
+    let transaction = data
+        .recurring
+        .iter_mut()
+        .find(|t| t.id == id)
+        .context("Recurring transaction not found")?;
+
+    // Summary lines are printed only once the edit is valid and saved
+    let summary = apply_recurring_changes(transaction, changes, Local::now().date_naive())?;
+
+    transaction.validate()?;
+    save_data(&data).await?;
+
+    for line in &summary {
+        println!("{}", line);
+    }
+    println!("Recurring transaction updated successfully.");
+
+    Ok(())
+}
+
+/// Change to a single occurrence of a recurring transaction
+#[derive(Debug, Clone, Copy)]
+pub enum OccurrenceEdit<'a> {
+    /// The occurrence does not happen this time
+    Skip,
+    /// The occurrence happens on another date
+    Move { to: &'a str },
+    /// The occurrence has a different amount this time
+    Amount(&'a str),
+    /// The occurrence was paid
+    Paid,
+    /// Drop all changes to the occurrence
+    Reset,
+}
+
+/// Executes the recurring skip/move/adjust/paid/reset commands
+/// Without a date, `paid` picks the latest occurrence up to today, `reset` the next
+/// occurrence from today on and the others the next one that has not been changed yet
+pub async fn execute_recurring_occurrence(
     id_str: &str,
-    amount: Option<&str>,
-    day: Option<u8>,
-    description: Option<&str>,
+    date: Option<&str>,
+    edit: OccurrenceEdit<'_>,
 ) -> Result<()> {
     let mut data = load_data().await?;
-    let id = parse_uuid(id_str, &data.recurring)?;
+    let id = resolve_recurring_id(id_str, &data.recurring)?;
 
     let transaction = data
         .recurring
@@ -168,66 +368,280 @@ pub async fn execute_recurring_edit(
         .find(|t| t.id == id)
         .context("Recurring transaction not found")?;
 
-    if let Some(amount_str) = amount {
-        transaction.amount = parse_amount(amount_str)?;
-        println!("Updated amount: {}", format_amount(transaction.amount));
+    let today = Local::now().date_naive();
+    let scheduled = match date {
+        Some(date_str) => {
+            let date = parse_date(date_str)?;
+            if !is_scheduled_on(transaction, date) {
+                bail!(
+                    "'{}' has no occurrence on {}. Use 'recurring occurrences {}' to see them.",
+                    transaction.description,
+                    date.format("%d.%m.%Y"),
+                    id_str
+                );
+            }
+            date
+        }
+        None if matches!(edit, OccurrenceEdit::Paid) => {
+            scheduled_dates(transaction, today - Duration::days(366), today)
+                .pop()
+                .context("No occurrence in the past year, pass --date")?
+        }
+        None => scheduled_dates(
+            transaction,
+            today - Duration::days(1),
+            today + Duration::days(366),
+        )
+        .into_iter()
+        .find(|&date| {
+            matches!(edit, OccurrenceEdit::Reset) || transaction.occurrence_override(date).is_none()
+        })
+        .context("No upcoming unchanged occurrence in the next year, pass --date")?,
+    };
+
+    match edit {
+        OccurrenceEdit::Skip => {
+            transaction.update_occurrence(scheduled, |o| o.status = OccurrenceStatus::Skipped);
+            println!(
+                "Skipped {} on {}",
+                transaction.description,
+                scheduled.format("%d.%m.%Y")
+            );
+        }
+        OccurrenceEdit::Move { to } => {
+            let to = parse_date(to)?;
+            if to != scheduled && occupied_by_other(transaction, scheduled, to) {
+                bail!(
+                    "'{}' already has an occurrence on {}",
+                    transaction.description,
+                    to.format("%d.%m.%Y")
+                );
+            }
+            // Moving back to the scheduled date just drops the move
+            let moved_to = (to != scheduled).then_some(to);
+            transaction.update_occurrence(scheduled, |o| o.moved_to = moved_to);
+            println!(
+                "Moved {} from {} to {}",
+                transaction.description,
+                scheduled.format("%d.%m.%Y"),
+                to.format("%d.%m.%Y")
+            );
+        }
+        OccurrenceEdit::Amount(amount_str) => {
+            let amount = parse_amount(amount_str)?;
+            if amount.is_zero() {
+                bail!("Amount must not be zero, use 'recurring skip' instead");
+            }
+            transaction.update_occurrence(scheduled, |o| o.amount = Some(amount));
+            println!(
+                "Amount of {} on {}: {}",
+                transaction.description,
+                scheduled.format("%d.%m.%Y"),
+                format_amount(amount)
+            );
+        }
+        OccurrenceEdit::Paid => {
+            transaction.update_occurrence(scheduled, |o| o.status = OccurrenceStatus::Confirmed);
+            println!(
+                "Marked {} on {} as paid",
+                transaction.description,
+                scheduled.format("%d.%m.%Y")
+            );
+        }
+        OccurrenceEdit::Reset => {
+            transaction.update_occurrence(scheduled, |o| *o = OccurrenceOverride::new(scheduled));
+            println!(
+                "Reset {} on {}",
+                transaction.description,
+                scheduled.format("%d.%m.%Y")
+            );
+        }
     }
 
-    if let Some(d) = day {
-        if !(1..=31).contains(&d) {
-            anyhow::bail!("Day must be between 1 and 31");
+    save_data(&data).await?;
+    Ok(())
+}
+
+/// Keeps occurrence overrides attached to their month after the day of month changed,
+/// by moving them to the date the rule now schedules in that month
+pub(crate) fn remap_overrides(transaction: &mut RecurringTransaction) {
+    for occurrence in &mut transaction.overrides {
+        if let Some(date) = get_transaction_date_in_month(occurrence.date, transaction.day_of_month)
+        {
+            occurrence.date = date;
+            // A move to the new scheduled date is no move anymore
+            if occurrence.moved_to == Some(date) {
+                occurrence.moved_to = None;
+            }
         }
-        transaction.day_of_month = d;
-        println!("Updated day of month: {}", d);
     }
+    transaction.overrides.retain(|o| !o.is_noop());
+    transaction.overrides.sort_by_key(|o| o.date);
+}
+
+/// True if an occurrence other than the one scheduled on `scheduled` happens on `date`
+fn occupied_by_other(
+    transaction: &RecurringTransaction,
+    scheduled: NaiveDate,
+    date: NaiveDate,
+) -> bool {
+    let scheduled_there = is_scheduled_on(transaction, date)
+        && transaction
+            .occurrence_override(date)
+            .is_none_or(|o| o.moved_to.is_none());
+    let moved_there = transaction
+        .overrides
+        .iter()
+        .any(|o| o.date != scheduled && o.moved_to == Some(date));
+    scheduled_there || moved_there
+}
+
+/// Executes recurring occurrences command: lists the occurrences of the next months
+/// together with every changed occurrence
+pub async fn execute_recurring_occurrences(id_str: &str, months: u32) -> Result<()> {
+    let data = load_data().await?;
+    let id = resolve_recurring_id(id_str, &data.recurring)?;
+
+    let transaction = data
+        .recurring
+        .iter()
+        .find(|t| t.id == id)
+        .context("Recurring transaction not found")?;
+
+    let today = Local::now().date_naive();
+    let end = today
+        .checked_add_months(chrono::Months::new(months))
+        .unwrap_or(today);
+    let mut dates = scheduled_dates(transaction, today - Duration::days(1), end);
+    dates.extend(transaction.overrides.iter().map(|o| o.date));
+    dates.sort();
+    dates.dedup();
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+
+    table.set_header(vec![
+        Cell::new("Scheduled").add_attribute(Attribute::Bold),
+        Cell::new("Moved to").add_attribute(Attribute::Bold),
+        Cell::new("Amount").add_attribute(Attribute::Bold),
+        Cell::new("Status").add_attribute(Attribute::Bold),
+    ]);
+
+    for scheduled in dates {
+        let occurrence = transaction.occurrence_override(scheduled);
+        let status = occurrence.map(|o| o.status).unwrap_or_default();
+        let amount = transaction.occurrence_amount(scheduled);
+        let is_past = occurrence.map_or(scheduled, |o| o.actual_date()) < today;
 
-    if let Some(desc) = description {
-        transaction.description = desc.to_string();
-        println!("Updated description: {}", desc);
+        table.add_row(vec![
+            Cell::new(scheduled.format("%d.%m.%Y")),
+            Cell::new(
+                occurrence
+                    .and_then(|o| o.moved_to)
+                    .map(|d| d.format("%d.%m.%Y").to_string())
+                    .unwrap_or_default(),
+            ),
+            Cell::new(format_amount(amount)).fg(if amount.is_sign_negative() {
+                Color::Red
+            } else {
+                Color::Green
+            }),
+            Cell::new(status.to_string()).fg(match status {
+                OccurrenceStatus::Confirmed => Color::Green,
+                OccurrenceStatus::Skipped => Color::DarkGrey,
+                OccurrenceStatus::Pending if is_past => Color::Yellow,
+                OccurrenceStatus::Pending => Color::White,
+            }),
+        ]);
     }
 
-    save_data(&data).await?;
-    println!("Recurring transaction updated successfully.");
+    println!("{}", transaction.description);
+    println!("{table}");
 
     Ok(())
 }
 
-/// Parses UUID from string, supports both full and short (8 char) format
-fn parse_uuid(s: &str, transactions: &[RecurringTransaction]) -> Result<Uuid> {
-    // Try full UUID first
-    if let Ok(uuid) = Uuid::parse_str(s) {
-        return Ok(uuid);
-    }
+/// Parses the optional min/max bounds of a variable amount, which must contain the
+/// expected amount and share its sign
+fn parse_range(
+    min: Option<&str>,
+    max: Option<&str>,
+    amount: Decimal,
+) -> Result<Option<AmountRange>> {
+    check_range(
+        min.map(parse_amount).transpose()?,
+        max.map(parse_amount).transpose()?,
+        amount,
+    )
+}
 
-    // Try short format (8 chars) - match against existing UUIDs by prefix
-    if s.len() >= 8 {
-        let prefix = s.to_lowercase();
-        let matching: Vec<_> = transactions
-            .iter()
-            .filter(|t| {
-                let uuid_str = t.id.to_string().to_lowercase();
-                uuid_str.starts_with(&prefix)
-            })
-            .collect();
-
-        match matching.len() {
-            0 => {
-                anyhow::bail!(
-                    "No recurring transaction found with ID starting with '{}'. Use 'recurring list' to see available IDs.",
-                    s
-                )
-            }
-            1 => return Ok(matching[0].id),
-            _ => {
-                anyhow::bail!(
-                    "Multiple transactions match '{}'. Use full UUID to be more specific. Use 'recurring list' to see all IDs.",
-                    s
-                )
-            }
-        }
+/// Builds the range from both bounds, which must contain the amount
+fn check_range(
+    min: Option<Decimal>,
+    max: Option<Decimal>,
+    amount: Decimal,
+) -> Result<Option<AmountRange>> {
+    let range = match (min, max) {
+        (Some(min), Some(max)) => AmountRange::new(min, max),
+        (None, None) => return Ok(None),
+        _ => anyhow::bail!("Specify both --min and --max for a variable amount"),
+    };
+
+    if !range.fits(amount) {
+        anyhow::bail!(
+            "Range {} must contain the amount {} and have the same sign",
+            format_range(&range),
+            format_amount(amount)
+        );
     }
 
-    anyhow::bail!(
-        "Invalid UUID format. Use full UUID or short format (first 8 characters) from 'recurring list'"
+    Ok(Some(range))
+}
+
+fn format_range(range: &AmountRange) -> String {
+    format!(
+        "{} … {}",
+        format_amount(range.min),
+        format_amount(range.max)
     )
 }
+
+/// Recurring transactions in the order `recurring list` numbers them
+fn list_order(transactions: &[RecurringTransaction]) -> Vec<&RecurringTransaction> {
+    let mut ordered: Vec<_> = transactions.iter().collect();
+    ordered.sort_by_key(|t| t.day_of_month);
+    ordered
+}
+
+/// Finds a recurring transaction by ID, ID prefix, list number or description
+fn resolve_recurring_id(s: &str, transactions: &[RecurringTransaction]) -> Result<Uuid> {
+    let candidates: Vec<_> = list_order(transactions)
+        .into_iter()
+        .map(IdCandidate::recurring)
+        .collect();
+    resolve_id(s, &candidates, "recurring transaction", "recurring list")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    #[test]
+    fn test_remap_overrides() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+        let mut rent = RecurringTransaction::new("Nájem".to_string(), Decimal::from(-5000), 20);
+        rent.update_occurrence(date(1, 20), |o| o.status = OccurrenceStatus::Confirmed);
+        rent.update_occurrence(date(2, 20), |o| o.moved_to = Some(date(2, 28)));
+        rent.update_occurrence(date(3, 20), |o| o.moved_to = Some(date(3, 31)));
+
+        rent.day_of_month = 31;
+        remap_overrides(&mut rent);
+
+        // Both moves now land on the newly scheduled dates and are dropped
+        let dates: Vec<_> = rent.overrides.iter().map(|o| o.date).collect();
+        assert_eq!(dates, vec![date(1, 31)]);
+        assert_eq!(rent.overrides[0].status, OccurrenceStatus::Confirmed);
+        assert!(is_scheduled_on(&rent, rent.overrides[0].date));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::Result;
+use crate::cli::format_amount;
+use crate::projection::build_report;
+use crate::storage::load_data_with_archive;
+use chrono::{Local, Months};
+use comfy_table::{Attribute, Cell, CellAlignment, Color, Table, presets::UTF8_FULL};
+use rust_decimal::Decimal;
+
+/// Executes report command: monthly and per-category totals of the last `months` months
+/// (including the current one) and `ahead` projected months
+pub async fn execute_report(months: u32, ahead: u32, json: bool) -> Result<()> {
+    if months == 0 {
+        anyhow::bail!("--months must be at least 1, the current month is always included");
+    }
+
+    // Pruned one-time transactions are history too
+    let data = load_data_with_archive().await?;
+    let today = Local::now().date_naive();
+
+    let first_month = today - Months::new(months.saturating_sub(1));
+    let last_month = today + Months::new(ahead);
+    let report = build_report(&data, first_month, last_month, today);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+
+    table.set_header(vec![
+        Cell::new("Month").add_attribute(Attribute::Bold),
+        Cell::new("Income").add_attribute(Attribute::Bold),
+        Cell::new("Expenses").add_attribute(Attribute::Bold),
+        Cell::new("Net").add_attribute(Attribute::Bold),
+        Cell::new("Year ago").add_attribute(Attribute::Bold),
+        Cell::new("Change").add_attribute(Attribute::Bold),
+    ]);
+
+    for month in &report.months {
+        let label = format!(
+            "{:02}/{}{}",
+            month.month,
+            month.year,
+            if month.projected { " *" } else { "" }
+        );
+
+        table.add_row(vec![
+            Cell::new(label),
+            amount_cell(month.income),
+            amount_cell(month.expenses),
+            amount_cell(month.net),
+            match month.net_year_ago {
+                Some(net) => amount_cell(net),
+                None => Cell::new("-").set_alignment(CellAlignment::Right),
+            },
+            match month.net_year_ago {
+                Some(net) => amount_cell(month.net - net),
+                None => Cell::new("-").set_alignment(CellAlignment::Right),
+            },
+        ]);
+    }
+
+    println!("{table}");
+    println!("* projected (month not over yet)");
+    println!();
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+
+    table.set_header(vec![
+        Cell::new("Category").add_attribute(Attribute::Bold),
+        Cell::new("Income").add_attribute(Attribute::Bold),
+        Cell::new("Expenses").add_attribute(Attribute::Bold),
+        Cell::new("Net").add_attribute(Attribute::Bold),
+        Cell::new("Monthly average").add_attribute(Attribute::Bold),
+    ]);
+
+    for category in &report.categories {
+        table.add_row(vec![
+            Cell::new(&category.category),
+            amount_cell(category.income),
+            amount_cell(category.expenses),
+            amount_cell(category.net),
+            amount_cell(category.monthly_average),
+        ]);
+    }
+
+    println!("{table}");
+    println!();
+    println!(
+        "Monthly average ({} – {}):",
+        report.from.format("%d.%m.%Y"),
+        report.to.format("%d.%m.%Y")
+    );
+    println!("  Income: {}", format_amount(report.average_income));
+    println!("  Expenses: {}", format_amount(report.average_expenses));
+    println!("  Net: {}", format_amount(report.average_net));
+
+    Ok(())
+}
+
+fn amount_cell(amount: Decimal) -> Cell {
+    let cell = Cell::new(format_amount(amount)).set_alignment(CellAlignment::Right);
+    if amount.is_sign_negative() {
+        cell.fg(Color::Red)
+    } else if amount.is_zero() {
+        cell
+    } else {
+        cell.fg(Color::Green)
+    }
+}
@@ -0,0 +1,257 @@
+use crate::Result;
+use crate::cli::{format_amount, format_date, is_interactive, prompt};
+use crate::models::{BalanceSnapshot, OneTimeTransaction, RecurringTransaction, normalize_text};
+use anyhow::bail;
+use uuid::Uuid;
+
+/// Shortest ID prefix that is matched against IDs
+const MIN_PREFIX_LEN: usize = 4;
+
+/// Entry that can be referred to by ID, ID prefix, description or its number in a list
+#[derive(Debug, Clone)]
+pub struct IdCandidate {
+    pub id: Uuid,
+    pub description: String,
+    /// Shown next to the description when several entries match, e.g. amount and date
+    pub detail: String,
+}
+
+impl IdCandidate {
+    pub fn recurring(txn: &RecurringTransaction) -> Self {
+        Self {
+            id: txn.id,
+            description: txn.description.clone(),
+            detail: format!("{}, day {}", format_amount(txn.amount), txn.day_of_month),
+        }
+    }
+
+    pub fn one_time(txn: &OneTimeTransaction) -> Self {
+        Self {
+            id: txn.id,
+            description: txn.description.clone(),
+            detail: format!("{}, {}", format_amount(txn.amount), format_date(txn.date)),
+        }
+    }
+
+    pub fn snapshot(snapshot: &BalanceSnapshot) -> Self {
+        Self {
+            id: snapshot.id,
+            description: format_date(snapshot.date),
+            detail: format_amount(snapshot.balance),
+        }
+    }
+
+    fn short_id(&self) -> String {
+        self.id.to_string().chars().take(8).collect()
+    }
+}
+
+/// Result of matching a query against the candidates
+#[derive(Debug)]
+enum Match<'a> {
+    None,
+    One(Uuid),
+    Ambiguous(Vec<&'a IdCandidate>),
+}
+
+/// Resolves what the user typed to an ID
+/// Accepts a full ID, an ID prefix of 4+ characters, the number shown by the list command
+/// (candidates must be in list order) or a description, ignoring case and diacritics.
+/// When several entries match, the user picks one, or the error lists them if stdin is
+/// not a terminal
+pub fn resolve_id(
+    query: &str,
+    candidates: &[IdCandidate],
+    kind: &str,
+    list_command: &str,
+) -> Result<Uuid> {
+    resolve(query, candidates, true, kind, list_command)
+}
+
+/// Like `resolve_id` without list numbers, for candidates gathered from several lists
+/// or from a list that does not number its entries
+pub fn resolve_id_unnumbered(
+    query: &str,
+    candidates: &[IdCandidate],
+    kind: &str,
+    list_command: &str,
+) -> Result<Uuid> {
+    resolve(query, candidates, false, kind, list_command)
+}
+
+fn resolve(
+    query: &str,
+    candidates: &[IdCandidate],
+    numbered: bool,
+    kind: &str,
+    list_command: &str,
+) -> Result<Uuid> {
+    match match_candidates(query, candidates, numbered) {
+        Match::One(id) => Ok(id),
+        Match::None => bail!(
+            "No {} matches '{}'. Use '{}' to see available entries.",
+            kind,
+            query,
+            list_command
+        ),
+        Match::Ambiguous(matches) => pick_candidate(query, &matches, kind),
+    }
+}
+
+fn match_candidates<'a>(query: &str, candidates: &'a [IdCandidate], numbered: bool) -> Match<'a> {
+    let query = query.trim();
+
+    if let Ok(uuid) = Uuid::parse_str(query) {
+        return if candidates.iter().any(|c| c.id == uuid) {
+            Match::One(uuid)
+        } else {
+            Match::None
+        };
+    }
+
+    // Number shown by the list command, counted from 1
+    if numbered
+        && let Ok(index) = query.parse::<usize>()
+        && (1..=candidates.len()).contains(&index)
+    {
+        return Match::One(candidates[index - 1].id);
+    }
+
+    let needle = normalize_text(query);
+    if needle.is_empty() {
+        return Match::None;
+    }
+
+    // A full description wins over partial matches, e.g. "Nájem" over "Nájem garáž"
+    let exact: Vec<_> = candidates
+        .iter()
+        .filter(|c| normalize_text(&c.description) == needle)
+        .collect();
+    match exact.as_slice() {
+        [] => {}
+        [candidate] => return Match::One(candidate.id),
+        _ => return Match::Ambiguous(exact),
+    }
+
+    let prefix = query.to_lowercase();
+    let matches: Vec<_> = candidates
+        .iter()
+        .filter(|c| {
+            normalize_text(&c.description).contains(&needle)
+                || (prefix.len() >= MIN_PREFIX_LEN && c.id.to_string().starts_with(&prefix))
+        })
+        .collect();
+    match matches.as_slice() {
+        [] => Match::None,
+        [candidate] => Match::One(candidate.id),
+        _ => Match::Ambiguous(matches),
+    }
+}
+
+/// Lets the user choose between several matches
+fn pick_candidate(query: &str, matches: &[&IdCandidate], kind: &str) -> Result<Uuid> {
+    let listing: String = matches
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            format!(
+                "  {}) {}  {} ({})\n",
+                i + 1,
+                c.short_id(),
+                c.description,
+                c.detail
+            )
+        })
+        .collect();
+
+    if !is_interactive() {
+        bail!(
+            "Several entries match '{}', use the ID to pick one:\n{}",
+            query,
+            listing.trim_end()
+        );
+    }
+
+    println!("Several entries match '{}':", query);
+    print!("{}", listing);
+    loop {
+        let answer = prompt(&format!("Pick the {} [1-{}]: ", kind, matches.len()))?;
+        if answer.is_empty() {
+            bail!("No {} picked", kind);
+        }
+        match answer.parse::<usize>() {
+            Ok(n) if (1..=matches.len()).contains(&n) => return Ok(matches[n - 1].id),
+            _ => println!("Enter a number from 1 to {}", matches.len()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+
+    fn candidates() -> Vec<IdCandidate> {
+        ["Netflix", "Nájem", "Nájem garáž", "Elektřina"]
+            .into_iter()
+            .map(|description| {
+                IdCandidate::recurring(&RecurringTransaction::new(
+                    description.to_string(),
+                    Decimal::from(-100),
+                    1,
+                ))
+            })
+            .collect()
+    }
+
+    fn matched(query: &str, candidates: &[IdCandidate]) -> Option<Uuid> {
+        match match_candidates(query, candidates, true) {
+            Match::One(id) => Some(id),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_match_candidates() {
+        let candidates = candidates();
+        let id = |i: usize| Some(candidates[i].id);
+
+        assert_eq!(matched("netflix", &candidates), id(0));
+        assert_eq!(matched("NAJEM", &candidates), id(1));
+        assert_eq!(matched("garaz", &candidates), id(2));
+        assert_eq!(matched("elektr", &candidates), id(3));
+        assert_eq!(matched("2", &candidates), id(1));
+        assert_eq!(matched(&candidates[3].short_id()[..4], &candidates), id(3));
+        assert_eq!(matched(&candidates[0].id.to_string(), &candidates), id(0));
+
+        assert!(matches!(
+            match_candidates("plyn", &candidates, true),
+            Match::None
+        ));
+        assert!(matches!(
+            match_candidates(&Uuid::new_v4().to_string(), &candidates, true),
+            Match::None
+        ));
+        assert!(matches!(
+            match_candidates("2", &candidates, false),
+            Match::None
+        ));
+    }
+
+    #[test]
+    fn test_ambiguous_match() {
+        let mut candidates = candidates();
+        match match_candidates("naj", &candidates, true) {
+            Match::Ambiguous(matches) => assert_eq!(matches.len(), 2),
+            other => panic!("expected ambiguous match, got {:?}", other),
+        }
+
+        // Two entries with the same description
+        candidates.push(candidates[0].clone());
+        candidates[4].id = Uuid::new_v4();
+        match match_candidates("Netflix", &candidates, true) {
+            Match::Ambiguous(matches) => assert_eq!(matches.len(), 2),
+            other => panic!("expected ambiguous match, got {:?}", other),
+        }
+    }
+}
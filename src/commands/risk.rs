@@ -0,0 +1,78 @@
+use crate::Result;
+use crate::cli::format_amount;
+use crate::commands::balance::parse_amount;
+use crate::config::get_display_settings;
+use crate::projection::{RiskOptions, project_cashflow, simulate_risk};
+use crate::storage::load_data;
+use comfy_table::{Attribute, Cell, CellAlignment, Color, Table, presets::UTF8_FULL};
+
+/// Executes risk command: Monte Carlo simulation of the lowest balance over the next N days
+pub async fn execute_risk(
+    days: i64,
+    runs: usize,
+    jitter_days: i64,
+    seed: Option<u64>,
+    threshold: Option<&str>,
+) -> Result<()> {
+    if runs == 0 {
+        anyhow::bail!("Number of runs must be at least 1");
+    }
+
+    let data = load_data().await?;
+
+    let threshold = match threshold {
+        Some(threshold) => parse_amount(threshold)?,
+        None => get_display_settings().await?.warning_threshold,
+    };
+
+    let projection = project_cashflow(&data, days)?;
+    let options = RiskOptions {
+        runs,
+        jitter_days,
+        seed: seed.unwrap_or_else(rand::random),
+        threshold,
+    };
+    let report = simulate_risk(&data, &projection, &options);
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+
+    table.set_header(vec![
+        Cell::new("Percentile").add_attribute(Attribute::Bold),
+        Cell::new("Lowest balance").add_attribute(Attribute::Bold),
+    ]);
+
+    for (percentile, balance) in &report.min_balance_percentiles {
+        table.add_row(vec![
+            Cell::new(format!("P{}", percentile)),
+            Cell::new(format_amount(*balance))
+                .set_alignment(CellAlignment::Right)
+                .fg(if balance.is_sign_negative() {
+                    Color::Red
+                } else if *balance < threshold {
+                    Color::Yellow
+                } else {
+                    Color::Cyan
+                }),
+        ]);
+    }
+
+    println!(
+        "Lowest balance over the next {} days ({} runs):",
+        days, report.runs
+    );
+    println!("{table}");
+    println!();
+    println!(
+        "Probability of dropping below 0 Kč: {:.1} %",
+        report.probability_below_zero * 100.0
+    );
+    println!(
+        "Probability of dropping below {}: {:.1} %",
+        format_amount(threshold),
+        report.probability_below_threshold * 100.0
+    );
+    println!("Seed: {} (pass --seed to reproduce)", options.seed);
+
+    Ok(())
+}
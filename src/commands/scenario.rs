@@ -0,0 +1,264 @@
+use crate::Result;
+use crate::commands::balance::{parse_amount, parse_date};
+use crate::commands::resolve::{IdCandidate, resolve_id_unnumbered};
+use crate::models::{
+    CashflowData, OneTimeTransaction, RecurringTransaction, Scenario, ScenarioChange,
+};
+use crate::storage::{load_data, save_data};
+use anyhow::Context;
+use chrono::{Local, NaiveDate};
+use comfy_table::{Attribute, Cell, Table, presets::UTF8_FULL};
+use uuid::Uuid;
+
+/// Executes scenario create command
+pub async fn execute_scenario_create(name: &str) -> Result<()> {
+    let mut data = load_data().await?;
+
+    if data.scenarios.iter().any(|s| s.name == name) {
+        anyhow::bail!("Scenario '{}' already exists", name);
+    }
+
+    data.scenarios.push(Scenario::new(name.to_string()));
+    save_data(&data).await?;
+
+    println!("Created scenario '{}'", name);
+    println!("Add changes with e.g.:");
+    println!(
+        "  cashflow scenario change-amount {} <id> --amount=-17000",
+        name
+    );
+    println!("  cashflow scenario remove {} <id>", name);
+
+    Ok(())
+}
+
+/// Executes scenario list command
+pub async fn execute_scenario_list() -> Result<()> {
+    let data = load_data().await?;
+
+    if data.scenarios.is_empty() {
+        println!("No scenarios found.");
+        println!("Create one with:");
+        println!("  cashflow scenario create <name>");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+
+    table.set_header(vec![
+        Cell::new("Name").add_attribute(Attribute::Bold),
+        Cell::new("Changes").add_attribute(Attribute::Bold),
+        Cell::new("Created").add_attribute(Attribute::Bold),
+    ]);
+
+    for scenario in &data.scenarios {
+        table.add_row(vec![
+            Cell::new(&scenario.name),
+            Cell::new(scenario.changes.len().to_string()),
+            Cell::new(scenario.created_at.format("%d.%m.%Y").to_string()),
+        ]);
+    }
+
+    println!("{table}");
+
+    Ok(())
+}
+
+/// Executes scenario show command: lists the changes of a scenario
+pub async fn execute_scenario_show(name: &str) -> Result<()> {
+    let data = load_data().await?;
+    let scenario = find_scenario(&data, name)?;
+
+    println!("Scenario '{}':", scenario.name);
+
+    if scenario.changes.is_empty() {
+        println!("  No changes yet.");
+        return Ok(());
+    }
+
+    for (index, change) in scenario.changes.iter().enumerate() {
+        println!("  {}. {}", index + 1, change.describe(&data));
+    }
+
+    println!();
+    println!(
+        "Compare with the real plan: cashflow plan --scenario {}",
+        name
+    );
+
+    Ok(())
+}
+
+/// Executes scenario delete command
+pub async fn execute_scenario_delete(name: &str) -> Result<()> {
+    let mut data = load_data().await?;
+
+    let index = data
+        .scenarios
+        .iter()
+        .position(|s| s.name == name)
+        .with_context(|| format!("Scenario '{}' not found", name))?;
+
+    data.scenarios.remove(index);
+    save_data(&data).await?;
+
+    println!("Deleted scenario '{}'", name);
+
+    Ok(())
+}
+
+/// Executes scenario add-recurring command
+pub async fn execute_scenario_add_recurring(
+    name: &str,
+    description: &str,
+    amount_str: &str,
+    day: u8,
+) -> Result<()> {
+    let amount = parse_amount(amount_str)?;
+    let transaction = RecurringTransaction::new(description.to_string(), amount, day);
+    transaction.validate()?;
+
+    add_change(name, ScenarioChange::AddRecurring(transaction)).await
+}
+
+/// Executes scenario add-one-time command
+pub async fn execute_scenario_add_one_time(
+    name: &str,
+    description: &str,
+    amount_str: &str,
+    date_str: &str,
+) -> Result<()> {
+    let amount = parse_amount(amount_str)?;
+    let date = parse_date(date_str)?;
+    let transaction = OneTimeTransaction::new(description.to_string(), amount, date);
+    transaction.validate()?;
+
+    add_change(name, ScenarioChange::AddOneTime(transaction)).await
+}
+
+/// Executes scenario remove command: removes a real transaction in the scenario
+/// from the effective date (default today) on
+pub async fn execute_scenario_remove(
+    name: &str,
+    id_str: &str,
+    effective: Option<&str>,
+) -> Result<()> {
+    let data = load_data().await?;
+    let id = resolve_transaction_id(id_str, &data)?;
+    let effective_from = parse_effective(effective)?;
+
+    add_change(name, ScenarioChange::Remove { id, effective_from }).await
+}
+
+/// Executes scenario change-amount command, the new amount applies from the effective
+/// date (default today) on
+pub async fn execute_scenario_change_amount(
+    name: &str,
+    id_str: &str,
+    amount_str: &str,
+    effective: Option<&str>,
+) -> Result<()> {
+    let data = load_data().await?;
+    let id = resolve_transaction_id(id_str, &data)?;
+    let amount = parse_amount(amount_str)?;
+    let effective_from = parse_effective(effective)?;
+
+    if amount.is_zero() {
+        anyhow::bail!("Amount must not be zero, use 'scenario remove' instead");
+    }
+
+    add_change(
+        name,
+        ScenarioChange::ChangeAmount {
+            id,
+            amount,
+            effective_from,
+        },
+    )
+    .await
+}
+
+/// Executes scenario drop-change command: removes a change by its number from 'scenario show'
+pub async fn execute_scenario_drop_change(name: &str, number: usize) -> Result<()> {
+    let mut data = load_data().await?;
+    let snapshot = data.clone();
+    let scenario = find_scenario_mut(&mut data, name)?;
+
+    if number == 0 || number > scenario.changes.len() {
+        anyhow::bail!(
+            "Scenario '{}' has no change number {}. Use 'scenario show {}' to see them.",
+            name,
+            number,
+            name
+        );
+    }
+
+    let removed = scenario.changes.remove(number - 1);
+    save_data(&data).await?;
+
+    println!("Dropped change: {}", removed.describe(&snapshot));
+
+    Ok(())
+}
+
+/// Appends a change to a scenario and saves
+async fn add_change(name: &str, change: ScenarioChange) -> Result<()> {
+    let mut data = load_data().await?;
+    let description = change.describe(&data);
+
+    find_scenario_mut(&mut data, name)?.changes.push(change);
+    save_data(&data).await?;
+
+    println!("Scenario '{}': {}", name, description);
+
+    Ok(())
+}
+
+/// Parses the date a change takes effect, default today
+fn parse_effective(effective: Option<&str>) -> Result<NaiveDate> {
+    match effective {
+        Some(date_str) => parse_date(date_str),
+        None => Ok(Local::now().date_naive()),
+    }
+}
+
+fn find_scenario<'a>(data: &'a CashflowData, name: &str) -> Result<&'a Scenario> {
+    data.scenarios
+        .iter()
+        .find(|s| s.name == name)
+        .with_context(|| {
+            format!(
+                "Scenario '{}' not found. Use 'scenario list' to see available scenarios.",
+                name
+            )
+        })
+}
+
+fn find_scenario_mut<'a>(data: &'a mut CashflowData, name: &str) -> Result<&'a mut Scenario> {
+    data.scenarios
+        .iter_mut()
+        .find(|s| s.name == name)
+        .with_context(|| {
+            format!(
+                "Scenario '{}' not found. Use 'scenario list' to see available scenarios.",
+                name
+            )
+        })
+}
+
+/// Finds a recurring or one-time transaction by ID, ID prefix or description
+fn resolve_transaction_id(s: &str, data: &CashflowData) -> Result<Uuid> {
+    let candidates: Vec<_> = data
+        .recurring
+        .iter()
+        .map(IdCandidate::recurring)
+        .chain(data.one_time.iter().map(IdCandidate::one_time))
+        .collect();
+    resolve_id_unnumbered(
+        s,
+        &candidates,
+        "transaction",
+        "recurring list' or 'one-time list",
+    )
+}
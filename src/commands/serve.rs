@@ -0,0 +1,33 @@
+use crate::Result;
+use crate::server::serve;
+use anyhow::Context;
+use std::net::SocketAddr;
+
+/// Environment variable with the bearer token, used when `--token` is not given
+pub const API_TOKEN_ENV: &str = "CASHFLOW_API_TOKEN";
+
+/// Executes serve command: runs the local REST API until interrupted
+pub async fn execute_serve(bind: &str, token: Option<&str>) -> Result<()> {
+    let bind: SocketAddr = bind
+        .parse()
+        .with_context(|| format!("Invalid address '{}', expected e.g. 127.0.0.1:8080", bind))?;
+
+    let token = token
+        .map(str::to_string)
+        .or_else(|| std::env::var(API_TOKEN_ENV).ok())
+        .filter(|t| !t.is_empty());
+
+    println!("Serving the cashflow API on http://{}", bind);
+    match &token {
+        Some(_) => println!("Requests to /api must send: Authorization: Bearer <token>"),
+        None if !bind.ip().is_loopback() => println!(
+            "Warning: no token set and {} is reachable from other machines. Use --token or {}.",
+            bind.ip(),
+            API_TOKEN_ENV
+        ),
+        None => {}
+    }
+    println!("Press Ctrl+C to stop.");
+
+    serve(bind, token).await
+}
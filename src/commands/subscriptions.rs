@@ -0,0 +1,111 @@
+use crate::Result;
+use crate::cli::format_amount;
+use crate::projection::audit_subscriptions;
+use crate::storage::load_data;
+use chrono::{Datelike, Local, NaiveDate};
+use comfy_table::{Attribute, Cell, CellAlignment, Color, Table, presets::UTF8_FULL};
+use rust_decimal::Decimal;
+
+/// Executes subscriptions command: audit of active recurring expenses
+pub async fn execute_subscriptions() -> Result<()> {
+    let data = load_data().await?;
+    let today = Local::now().date_naive();
+
+    let subscriptions = audit_subscriptions(&data, today);
+
+    if subscriptions.is_empty() {
+        println!("No active recurring expenses found.");
+        return Ok(());
+    }
+
+    let mut table = Table::new();
+    table.load_preset(UTF8_FULL);
+
+    table.set_header(vec![
+        Cell::new("ID").add_attribute(Attribute::Bold),
+        Cell::new("Description").add_attribute(Attribute::Bold),
+        Cell::new("Category").add_attribute(Attribute::Bold),
+        Cell::new("Monthly").add_attribute(Attribute::Bold),
+        Cell::new("Yearly").add_attribute(Attribute::Bold),
+        Cell::new("Paying for").add_attribute(Attribute::Bold),
+        Cell::new("Next charge").add_attribute(Attribute::Bold),
+    ]);
+
+    for subscription in &subscriptions {
+        let id_short = subscription
+            .id
+            .to_string()
+            .chars()
+            .take(8)
+            .collect::<String>();
+        let description = if subscription.duplicate {
+            Cell::new(format!("{} ⚠", subscription.description)).fg(Color::Yellow)
+        } else {
+            Cell::new(&subscription.description)
+        };
+
+        table.add_row(vec![
+            Cell::new(id_short),
+            description,
+            Cell::new(subscription.category.as_deref().unwrap_or("")),
+            Cell::new(format_amount(subscription.monthly_cost)).set_alignment(CellAlignment::Right),
+            Cell::new(format_amount(subscription.yearly_cost)).set_alignment(CellAlignment::Right),
+            Cell::new(format_age(subscription.created_at, today)),
+            Cell::new(
+                subscription
+                    .next_charge
+                    .map(|date| date.format("%d.%m.%Y").to_string())
+                    .unwrap_or_default(),
+            ),
+        ]);
+    }
+
+    println!("{table}");
+
+    if subscriptions.iter().any(|s| s.duplicate) {
+        println!("⚠ = same description as another recurring expense, possibly paid twice");
+    }
+
+    let monthly: Decimal = subscriptions.iter().map(|s| s.monthly_cost).sum();
+    println!();
+    println!("Burn rate: {} per month", format_amount(monthly));
+    println!(
+        "           {} per year",
+        format_amount(monthly * Decimal::from(12))
+    );
+
+    Ok(())
+}
+
+/// Human readable time between `since` and `today`, e.g. "2 y 3 m"
+fn format_age(since: NaiveDate, today: NaiveDate) -> String {
+    let mut months =
+        (today.year() - since.year()) * 12 + today.month() as i32 - since.month() as i32;
+    if today.day() < since.day() {
+        months -= 1;
+    }
+
+    match months {
+        m if m < 1 => format!("{} d", (today - since).num_days().max(0)),
+        m if m < 12 => format!("{} m", m),
+        m if m % 12 == 0 => format!("{} y", m / 12),
+        m => format!("{} y {} m", m / 12, m % 12),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(date(2025, 3, 1), date(2025, 3, 11)), "10 d");
+        assert_eq!(format_age(date(2025, 1, 15), date(2025, 3, 14)), "1 m");
+        assert_eq!(format_age(date(2023, 3, 1), date(2025, 3, 1)), "2 y");
+        assert_eq!(format_age(date(2022, 1, 1), date(2025, 4, 1)), "3 y 3 m");
+    }
+}
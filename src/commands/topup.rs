@@ -0,0 +1,105 @@
+use crate::Result;
+use crate::cli::{format_amount, format_date};
+use crate::commands::balance::parse_amount;
+use crate::config::get_display_settings;
+use crate::models::OneTimeTransaction;
+use crate::projection::{TopUp, monthly_topup_schedule, project_cashflow, required_topup};
+use crate::storage::{load_data, save_data};
+use comfy_table::{Attribute, Cell, CellAlignment, Table, presets::UTF8_FULL};
+
+/// Executes topup command: how much must be transferred, and by when, to keep the
+/// balance above the warning threshold
+pub async fn execute_topup(
+    days: i64,
+    threshold: Option<&str>,
+    monthly: bool,
+    create: bool,
+    description: &str,
+) -> Result<()> {
+    let mut data = load_data().await?;
+
+    let threshold = match threshold {
+        Some(threshold) => parse_amount(threshold)?,
+        None => get_display_settings().await?.warning_threshold,
+    };
+
+    let projection = project_cashflow(&data, days)?;
+
+    let topups: Vec<TopUp> = if monthly {
+        monthly_topup_schedule(&projection, threshold)
+    } else {
+        required_topup(&projection, threshold).into_iter().collect()
+    };
+
+    if topups.is_empty() {
+        println!(
+            "Balance stays above {} for the next {} days. No top-up needed.",
+            format_amount(threshold),
+            days
+        );
+        return Ok(());
+    }
+
+    if let [topup] = topups.as_slice() {
+        println!(
+            "Transfer at least {} by {} to stay above {}.",
+            format_amount(topup.amount),
+            format_date(topup.date),
+            format_amount(threshold)
+        );
+        println!(
+            "Lowest projected balance without it: {} ({})",
+            format_amount(topup.lowest_balance),
+            format_date(topup.lowest_balance_date)
+        );
+    } else {
+        let mut table = Table::new();
+        table.load_preset(UTF8_FULL);
+
+        table.set_header(vec![
+            Cell::new("Transfer by").add_attribute(Attribute::Bold),
+            Cell::new("Amount").add_attribute(Attribute::Bold),
+            Cell::new("Lowest balance without it").add_attribute(Attribute::Bold),
+        ]);
+
+        for topup in &topups {
+            table.add_row(vec![
+                Cell::new(format_date(topup.date)),
+                Cell::new(format_amount(topup.amount)).set_alignment(CellAlignment::Right),
+                Cell::new(format!(
+                    "{} ({})",
+                    format_amount(topup.lowest_balance),
+                    format_date(topup.lowest_balance_date)
+                ))
+                .set_alignment(CellAlignment::Right),
+            ]);
+        }
+
+        println!(
+            "Monthly transfers to stay above {}:",
+            format_amount(threshold)
+        );
+        println!("{table}");
+        println!(
+            "Total: {}",
+            format_amount(topups.iter().map(|t| t.amount).sum())
+        );
+    }
+
+    if create {
+        for topup in &topups {
+            let transaction =
+                OneTimeTransaction::new(description.to_string(), topup.amount, topup.date);
+            data.one_time.push(transaction);
+        }
+        save_data(&data).await?;
+
+        println!();
+        println!("Added {} one-time transaction(s).", topups.len());
+    } else {
+        println!();
+        println!("Add them to the plan with --create.");
+    }
+
+    Ok(())
+}
@@ -1,20 +1,292 @@
 use crate::Result;
 use anyhow::Context;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 use tokio::fs as async_fs;
 
-/// Configuration stored in ~/.cashflowrc
+/// Name that refers to the top-level (non-profile) settings
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Profile selected with the global --profile flag, takes precedence over the config
+static PROFILE_OVERRIDE: OnceLock<String> = OnceLock::new();
+
+/// Configuration stored in $XDG_CONFIG_HOME/cashflow/config.toml (legacy: ~/.cashflowrc)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CashflowConfig {
     /// Path to data directory (where data.ron is stored)
+    #[serde(default = "get_default_data_dir")]
     pub data_dir: PathBuf,
+
+    /// Automatically archive one-time transactions and snapshots older than this many days
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_prune_days: Option<u32>,
+
+    /// Profile used when --profile is not given (None means the top-level settings)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+
+    /// Display settings for the top-level (default) data set
+    #[serde(default)]
+    pub display: DisplaySettings,
+
+    /// Named profiles, each with its own data directory and display settings
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, Profile>,
+
+    /// Where `cashflow check` sends alerts
+    #[serde(default, skip_serializing_if = "NotifySettings::is_empty")]
+    pub notify: NotifySettings,
 }
 
 impl Default for CashflowConfig {
     fn default() -> Self {
         Self {
             data_dir: get_default_data_dir(),
+            auto_prune_days: None,
+            active_profile: None,
+            display: DisplaySettings::default(),
+            profiles: BTreeMap::new(),
+            notify: NotifySettings::default(),
+        }
+    }
+}
+
+/// Named data set (e.g. household vs side-business)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    /// Path to data directory of this profile
+    pub data_dir: PathBuf,
+
+    #[serde(default)]
+    pub display: DisplaySettings,
+}
+
+/// Settings affecting how the plan is displayed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DisplaySettings {
+    /// Balances below this amount are highlighted
+    #[serde(default = "default_warning_threshold")]
+    pub warning_threshold: Decimal,
+
+    /// Show past transactions in the plan by default
+    #[serde(default)]
+    pub show_past: bool,
+
+    /// Warn when the latest balance snapshot is older than this many days
+    #[serde(default = "default_stale_snapshot_days")]
+    pub stale_snapshot_days: i64,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            warning_threshold: default_warning_threshold(),
+            show_past: false,
+            stale_snapshot_days: default_stale_snapshot_days(),
+        }
+    }
+}
+
+fn default_warning_threshold() -> Decimal {
+    Decimal::from(10_000)
+}
+
+fn default_stale_snapshot_days() -> i64 {
+    14
+}
+
+/// Notifiers used by `cashflow check` when a rule fails, all of them are optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotifySettings {
+    /// Shell command to run, gets the message on stdin and in CASHFLOW_ALERT_MESSAGE
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// URL that receives the alerts as a JSON POST request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+
+    /// Mail server for e-mail alerts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smtp: Option<SmtpSettings>,
+}
+
+impl NotifySettings {
+    pub fn is_empty(&self) -> bool {
+        self.command.is_none() && self.webhook.is_none() && self.smtp.is_none()
+    }
+}
+
+/// SMTP server and addresses for e-mail alerts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmtpSettings {
+    pub host: String,
+
+    /// Defaults to the usual port of the chosen security (465, 587 or 25)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+
+    #[serde(default)]
+    pub security: SmtpSecurity,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    pub from: String,
+
+    pub to: Vec<String>,
+}
+
+/// How the connection to the SMTP server is encrypted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// TLS from the start (usually port 465)
+    Tls,
+    /// Plain connection upgraded with STARTTLS (usually port 587)
+    #[default]
+    Starttls,
+    /// Unencrypted, only for local relays
+    None,
+}
+
+impl CashflowConfig {
+    /// Name of the profile in effect: --profile flag, then CASHFLOW_PROFILE, then config
+    pub fn active_profile_name(&self) -> Option<String> {
+        PROFILE_OVERRIDE
+            .get()
+            .cloned()
+            .or_else(|| std::env::var("CASHFLOW_PROFILE").ok())
+            .or_else(|| self.active_profile.clone())
+            .filter(|name| name != DEFAULT_PROFILE)
+    }
+
+    /// Looks up a profile by name, `None` name means the top-level settings
+    pub fn profile(&self, name: Option<&str>) -> Result<(&PathBuf, &DisplaySettings)> {
+        match name {
+            None => Ok((&self.data_dir, &self.display)),
+            Some(name) => {
+                let profile = self.profiles.get(name).with_context(|| {
+                    format!(
+                        "Unknown profile '{}'. Use 'cashflow config profile list' to see available profiles.",
+                        name
+                    )
+                })?;
+                Ok((&profile.data_dir, &profile.display))
+            }
+        }
+    }
+
+    /// Data directory of the active profile
+    pub fn active_data_dir(&self) -> Result<&PathBuf> {
+        Ok(self.profile(self.active_profile_name().as_deref())?.0)
+    }
+
+    /// Display settings of the active profile
+    pub fn active_display(&self) -> Result<&DisplaySettings> {
+        Ok(self.profile(self.active_profile_name().as_deref())?.1)
+    }
+}
+
+/// Selects the profile for this process (from the global --profile flag)
+pub fn set_profile_override(name: String) {
+    let _ = PROFILE_OVERRIDE.set(name);
+}
+
+/// Where the config file was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// $XDG_CONFIG_HOME/cashflow/config.toml
+    Xdg,
+    /// ~/.cashflowrc (used before XDG support)
+    Legacy,
+}
+
+/// Resolved config file location
+#[derive(Debug, Clone)]
+pub struct ConfigLocation {
+    pub path: PathBuf,
+    pub source: ConfigSource,
+    pub exists: bool,
+    /// Legacy config file that also exists but is ignored because the XDG one wins
+    pub shadowed_legacy: Option<PathBuf>,
+}
+
+/// Base directories used to resolve config and data locations
+#[derive(Debug, Clone)]
+pub struct BaseDirs {
+    pub home: PathBuf,
+    pub config_home: PathBuf,
+    pub data_home: PathBuf,
+}
+
+impl BaseDirs {
+    /// Resolves base directories from the environment
+    /// Relative XDG paths are ignored, as required by the XDG Base Directory spec
+    pub fn from_env() -> Result<Self> {
+        let home = dirs::home_dir().context("Failed to get home directory")?;
+        let xdg_dir = |var: &str, fallback: PathBuf| {
+            std::env::var_os(var)
+                .map(PathBuf::from)
+                .filter(|p| p.is_absolute())
+                .unwrap_or(fallback)
+        };
+
+        Ok(Self {
+            config_home: xdg_dir("XDG_CONFIG_HOME", home.join(".config")),
+            data_home: xdg_dir("XDG_DATA_HOME", home.join(".local").join("share")),
+            home,
+        })
+    }
+
+    pub fn xdg_config_path(&self) -> PathBuf {
+        self.config_home.join("cashflow").join("config.toml")
+    }
+
+    pub fn legacy_config_path(&self) -> PathBuf {
+        self.home.join(".cashflowrc")
+    }
+
+    pub fn xdg_data_dir(&self) -> PathBuf {
+        self.data_home.join("cashflow")
+    }
+
+    pub fn legacy_data_dir(&self) -> PathBuf {
+        self.home.join(".cashflow")
+    }
+
+    /// Picks the config file: XDG wins if it exists, then legacy, new files go to XDG
+    pub fn locate_config(&self) -> ConfigLocation {
+        let xdg = self.xdg_config_path();
+        let legacy = self.legacy_config_path();
+
+        if xdg.exists() {
+            ConfigLocation {
+                path: xdg,
+                source: ConfigSource::Xdg,
+                exists: true,
+                shadowed_legacy: legacy.exists().then_some(legacy),
+            }
+        } else if legacy.exists() {
+            ConfigLocation {
+                path: legacy,
+                source: ConfigSource::Legacy,
+                exists: true,
+                shadowed_legacy: None,
+            }
+        } else {
+            ConfigLocation {
+                path: xdg,
+                source: ConfigSource::Xdg,
+                exists: false,
+                shadowed_legacy: None,
+            }
         }
     }
 }
@@ -23,7 +295,7 @@ impl Default for CashflowConfig {
 /// Priority:
 /// 1. CASHFLOW_DATA_DIR environment variable
 /// 2. Debug builds: ./dev-data (local to repo, git-ignored)
-/// 3. Release builds: ~/.cashflow
+/// 3. Release builds: $XDG_DATA_HOME/cashflow (~/.local/share/cashflow)
 fn get_default_data_dir() -> PathBuf {
     // Check environment variable first
     if let Ok(env_dir) = std::env::var("CASHFLOW_DATA_DIR") {
@@ -39,51 +311,142 @@ fn get_default_data_dir() -> PathBuf {
 
     #[cfg(not(debug_assertions))]
     {
-        let home = dirs::home_dir().expect("Failed to get home directory");
-        home.join(".cashflow")
+        BaseDirs::from_env()
+            .expect("Failed to get home directory")
+            .xdg_data_dir()
     }
 }
 
-/// Gets the path to the config file (~/.cashflowrc)
+/// Gets the path to the config file (XDG location, or ~/.cashflowrc if not migrated yet)
 pub fn get_config_path() -> Result<PathBuf> {
-    let home = dirs::home_dir().context("Failed to get home directory")?;
-    Ok(home.join(".cashflowrc"))
+    Ok(locate_config()?.path)
+}
+
+/// Resolves which config file is in use
+pub fn locate_config() -> Result<ConfigLocation> {
+    Ok(BaseDirs::from_env()?.locate_config())
+}
+
+/// Moves ~/.cashflowrc to the XDG config location if there's no XDG config yet
+/// Returns the new path if the file was migrated
+pub async fn migrate_legacy_config(dirs: &BaseDirs) -> Result<Option<PathBuf>> {
+    let legacy = dirs.legacy_config_path();
+    let xdg = dirs.xdg_config_path();
+
+    if !legacy.exists() || xdg.exists() {
+        return Ok(None);
+    }
+
+    if let Some(parent) = xdg.parent() {
+        async_fs::create_dir_all(parent)
+            .await
+            .context("Failed to create config directory")?;
+    }
+
+    // Copy then remove, so the config survives moving across filesystems
+    async_fs::copy(&legacy, &xdg)
+        .await
+        .context("Failed to copy legacy config file")?;
+    async_fs::remove_file(&legacy)
+        .await
+        .context("Failed to remove legacy config file")?;
+
+    Ok(Some(xdg))
+}
+
+/// Moves ~/.cashflow to the XDG data location if the config still points there
+/// (or relies on the default) and the XDG data directory doesn't exist yet
+/// Returns true if the config was changed
+pub async fn migrate_legacy_data_dir(dirs: &BaseDirs, config: &mut CashflowConfig) -> bool {
+    let legacy = dirs.legacy_data_dir();
+    let xdg = dirs.xdg_data_dir();
+
+    let uses_default = config.data_dir == legacy || config.data_dir == xdg;
+    if !uses_default || !legacy.exists() || xdg.exists() {
+        return false;
+    }
+
+    let moved = match xdg.parent() {
+        Some(parent) => async_fs::create_dir_all(parent).await.is_ok(),
+        None => true,
+    } && async_fs::rename(&legacy, &xdg).await.is_ok();
+
+    if moved {
+        eprintln!(
+            "Moved data directory {} → {}",
+            legacy.display(),
+            xdg.display()
+        );
+        config.data_dir = xdg;
+    } else {
+        // Keep using the legacy directory, e.g. when it's on another filesystem
+        eprintln!(
+            "Could not move data directory {} to {}, move it manually to finish the migration",
+            legacy.display(),
+            xdg.display()
+        );
+        config.data_dir = legacy;
+    }
+
+    true
 }
 
-/// Loads config from ~/.cashflowrc
+/// Loads config from the config file (XDG or legacy ~/.cashflowrc)
 /// Returns default config if file doesn't exist
-/// In debug builds, ignores .cashflowrc and uses local dev-data/ directory
+/// Legacy config and data locations are migrated to XDG on first run
+/// In debug builds, ignores the config file and uses local dev-data/ directory
 pub async fn load_config() -> Result<CashflowConfig> {
-    // In debug builds, skip .cashflowrc and use local dev directory
+    // In debug builds, skip the config file and use local dev directory
     #[cfg(debug_assertions)]
     {
-        return Ok(CashflowConfig::default());
+        Ok(CashflowConfig::default())
     }
 
-    // In release builds, respect .cashflowrc
+    // In release builds, respect the config file
     #[cfg(not(debug_assertions))]
     {
-        let config_path = get_config_path()?;
+        let dirs = BaseDirs::from_env()?;
 
-        if !config_path.exists() {
-            // Return default config if file doesn't exist
-            return Ok(CashflowConfig::default());
+        if let Some(new_path) = migrate_legacy_config(&dirs).await? {
+            eprintln!(
+                "Moved config file {} → {}",
+                dirs.legacy_config_path().display(),
+                new_path.display()
+            );
         }
 
-        let contents = async_fs::read_to_string(&config_path)
-            .await
-            .context("Failed to read config file")?;
+        let config_path = dirs.locate_config().path;
+
+        let mut config = if config_path.exists() {
+            let contents = async_fs::read_to_string(&config_path)
+                .await
+                .context("Failed to read config file")?;
 
-        let config: CashflowConfig =
-            toml::from_str(&contents).context("Failed to parse config file")?;
+            toml::from_str(&contents).context("Failed to parse config file")?
+        } else {
+            // Use default config if file doesn't exist
+            CashflowConfig::default()
+        };
+
+        if migrate_legacy_data_dir(&dirs, &mut config).await && config_path.exists() {
+            save_config(&config).await?;
+        }
 
         Ok(config)
     }
 }
 
-/// Saves config to ~/.cashflowrc
+/// Saves config to the config file, creating the XDG config directory if needed
 pub async fn save_config(config: &CashflowConfig) -> Result<()> {
-    let config_path = get_config_path()?;
+    let dirs = BaseDirs::from_env()?;
+    migrate_legacy_config(&dirs).await?;
+
+    let config_path = dirs.locate_config().path;
+    if let Some(parent) = config_path.parent() {
+        async_fs::create_dir_all(parent)
+            .await
+            .context("Failed to create config directory")?;
+    }
 
     let contents = toml::to_string_pretty(config).context("Failed to serialize config")?;
 
@@ -103,13 +466,83 @@ pub async fn get_data_dir() -> Result<PathBuf> {
     }
 
     let config = load_config().await?;
-    Ok(config.data_dir)
+    Ok(config.active_data_dir()?.clone())
 }
 
-/// Sets the data directory path in config
+/// Gets the display settings of the active profile
+pub async fn get_display_settings() -> Result<DisplaySettings> {
+    let config = load_config().await?;
+    Ok(config.active_display()?.clone())
+}
+
+/// Gets the notifier settings for `cashflow check`
+pub async fn get_notify_settings() -> Result<NotifySettings> {
+    Ok(load_config().await?.notify)
+}
+
+/// Sets the data directory path of the active profile in config
 pub async fn set_data_dir(path: PathBuf) -> Result<()> {
     let mut config = load_config().await?;
-    config.data_dir = path;
+    match config.active_profile_name() {
+        Some(name) => {
+            config
+                .profiles
+                .get_mut(&name)
+                .with_context(|| format!("Unknown profile '{}'", name))?
+                .data_dir = path;
+        }
+        None => config.data_dir = path,
+    }
+    save_config(&config).await?;
+    Ok(())
+}
+
+/// Adds (or replaces) a named profile in config
+pub async fn add_profile(name: &str, profile: Profile) -> Result<()> {
+    if name == DEFAULT_PROFILE {
+        anyhow::bail!(
+            "'{}' is reserved for the top-level settings",
+            DEFAULT_PROFILE
+        );
+    }
+
+    let mut config = load_config().await?;
+    config.profiles.insert(name.to_string(), profile);
+    save_config(&config).await?;
+    Ok(())
+}
+
+/// Removes a named profile from config (its data directory is left untouched)
+pub async fn remove_profile(name: &str) -> Result<Profile> {
+    let mut config = load_config().await?;
+    let profile = config
+        .profiles
+        .remove(name)
+        .with_context(|| format!("Unknown profile '{}'", name))?;
+    if config.active_profile.as_deref() == Some(name) {
+        config.active_profile = None;
+    }
+    save_config(&config).await?;
+    Ok(profile)
+}
+
+/// Makes a profile the default for future runs
+pub async fn use_profile(name: &str) -> Result<()> {
+    let mut config = load_config().await?;
+    if name == DEFAULT_PROFILE {
+        config.active_profile = None;
+    } else {
+        config.profile(Some(name))?;
+        config.active_profile = Some(name.to_string());
+    }
+    save_config(&config).await?;
+    Ok(())
+}
+
+/// Sets the automatic pruning policy in config (None disables it)
+pub async fn set_auto_prune_days(days: Option<u32>) -> Result<()> {
+    let mut config = load_config().await?;
+    config.auto_prune_days = days;
     save_config(&config).await?;
     Ok(())
 }
@@ -126,9 +559,9 @@ mod tests {
         #[cfg(debug_assertions)]
         assert_eq!(config.data_dir, PathBuf::from("./dev-data"));
 
-        // In release builds, uses ~/.cashflow
+        // In release builds, uses $XDG_DATA_HOME/cashflow
         #[cfg(not(debug_assertions))]
-        assert!(config.data_dir.to_string_lossy().contains(".cashflow"));
+        assert!(config.data_dir.ends_with("cashflow"));
     }
 
     #[test]
@@ -137,4 +570,138 @@ mod tests {
         let toml = toml::to_string_pretty(&config).unwrap();
         assert!(toml.contains("data_dir"));
     }
+
+    #[test]
+    fn test_parse_notify_settings() {
+        let config: CashflowConfig = toml::from_str(
+            r#"
+            [notify]
+            command = "notify-send Cashflow"
+
+            [notify.smtp]
+            host = "smtp.example.com"
+            security = "tls"
+            from = "cashflow@example.com"
+            to = ["me@example.com"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.notify.command.as_deref(),
+            Some("notify-send Cashflow")
+        );
+        let smtp = config.notify.smtp.unwrap();
+        assert_eq!(smtp.security, SmtpSecurity::Tls);
+        assert_eq!(smtp.port, None);
+        assert_eq!(smtp.to, vec!["me@example.com".to_string()]);
+
+        assert!(CashflowConfig::default().notify.is_empty());
+    }
+
+    /// Creates a unique fake home directory under the system temp dir
+    fn temp_base_dirs() -> BaseDirs {
+        let home = std::env::temp_dir().join(format!("cashflow-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&home).unwrap();
+
+        BaseDirs {
+            config_home: home.join(".config"),
+            data_home: home.join(".local").join("share"),
+            home,
+        }
+    }
+
+    #[test]
+    fn test_locate_config() {
+        let dirs = temp_base_dirs();
+
+        // Nothing exists yet: new config goes to XDG
+        let location = dirs.locate_config();
+        assert_eq!(location.source, ConfigSource::Xdg);
+        assert!(!location.exists);
+
+        // Only legacy exists
+        std::fs::write(dirs.legacy_config_path(), "").unwrap();
+        let location = dirs.locate_config();
+        assert_eq!(location.source, ConfigSource::Legacy);
+        assert!(location.exists);
+
+        // Both exist: XDG wins and legacy is reported as shadowed
+        std::fs::create_dir_all(dirs.xdg_config_path().parent().unwrap()).unwrap();
+        std::fs::write(dirs.xdg_config_path(), "").unwrap();
+        let location = dirs.locate_config();
+        assert_eq!(location.source, ConfigSource::Xdg);
+        assert_eq!(location.shadowed_legacy, Some(dirs.legacy_config_path()));
+
+        std::fs::remove_dir_all(&dirs.home).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_migrate_legacy_locations() -> Result<()> {
+        let dirs = temp_base_dirs();
+        std::fs::write(dirs.legacy_config_path(), "data_dir = \"x\"")?;
+        std::fs::create_dir_all(dirs.legacy_data_dir())?;
+        std::fs::write(dirs.legacy_data_dir().join("data.ron"), "()")?;
+
+        let migrated = migrate_legacy_config(&dirs).await?;
+        assert_eq!(migrated, Some(dirs.xdg_config_path()));
+        assert!(!dirs.legacy_config_path().exists());
+        assert!(dirs.xdg_config_path().exists());
+
+        let mut config = CashflowConfig {
+            data_dir: dirs.legacy_data_dir(),
+            ..CashflowConfig::default()
+        };
+        assert!(migrate_legacy_data_dir(&dirs, &mut config).await);
+        assert_eq!(config.data_dir, dirs.xdg_data_dir());
+        assert!(dirs.xdg_data_dir().join("data.ron").exists());
+
+        // Custom data directories are left alone
+        let mut config = CashflowConfig {
+            data_dir: dirs.home.join("custom"),
+            ..CashflowConfig::default()
+        };
+        assert!(!migrate_legacy_data_dir(&dirs, &mut config).await);
+
+        std::fs::remove_dir_all(&dirs.home)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_config_without_profiles() {
+        let config: CashflowConfig = toml::from_str("data_dir = \"/data\"").unwrap();
+
+        assert!(config.profiles.is_empty());
+        assert_eq!(config.display.warning_threshold, Decimal::from(10_000));
+    }
+
+    #[test]
+    fn test_profile_resolution() {
+        let mut config = CashflowConfig::default();
+        config.profiles.insert(
+            "business".to_string(),
+            Profile {
+                data_dir: PathBuf::from("/data/business"),
+                display: DisplaySettings {
+                    warning_threshold: Decimal::from(50_000),
+                    show_past: true,
+                    stale_snapshot_days: 30,
+                },
+            },
+        );
+
+        let (data_dir, display) = config.profile(Some("business")).unwrap();
+        assert_eq!(data_dir, &PathBuf::from("/data/business"));
+        assert_eq!(display.warning_threshold, Decimal::from(50_000));
+
+        let (data_dir, _) = config.profile(None).unwrap();
+        assert_eq!(data_dir, &config.data_dir);
+
+        assert!(config.profile(Some("missing")).is_err());
+
+        // Round-trips through TOML
+        let toml = toml::to_string_pretty(&config).unwrap();
+        let parsed: CashflowConfig = toml::from_str(&toml).unwrap();
+        assert!(parsed.profiles.contains_key("business"));
+    }
 }
@@ -3,6 +3,7 @@ pub mod commands;
 pub mod config;
 pub mod models;
 pub mod projection;
+pub mod server;
 pub mod storage;
 
 pub use anyhow::Result;
@@ -1,33 +1,178 @@
 use cashflow::Result;
-use cashflow::cli::{BalanceAction, Cli, Commands, ConfigAction, OneTimeAction, RecurringAction};
+use cashflow::cli::{
+    AccountAction, BalanceAction, BudgetAction, Cli, Commands, ConfigAction, GoalAction,
+    LoanAction, OneTimeAction, ProfileAction, RecurringAction, ScenarioAction,
+};
 use cashflow::commands::{
-    execute_balance_set, execute_balance_show, execute_config_set_data_dir, execute_config_show,
-    execute_export, execute_one_time_add, execute_one_time_delete, execute_one_time_edit,
-    execute_one_time_list, execute_plan, execute_recurring_add, execute_recurring_delete,
-    execute_recurring_disable, execute_recurring_edit, execute_recurring_enable,
-    execute_recurring_list,
+    OccurrenceEdit, RecurringEdit, execute_account_set, execute_account_show, execute_add,
+    execute_balance_delete, execute_balance_diff, execute_balance_list, execute_balance_set,
+    execute_balance_show, execute_budget_delete, execute_budget_set, execute_budget_show,
+    execute_check, execute_config_profile_add, execute_config_profile_list,
+    execute_config_profile_remove, execute_config_profile_use, execute_config_set_auto_prune,
+    execute_config_set_data_dir, execute_config_show, execute_doctor, execute_export,
+    execute_goal_add, execute_goal_delete, execute_goal_edit, execute_goal_fund,
+    execute_goals_list, execute_loan_add, execute_loan_list, execute_metrics, execute_one_time_add,
+    execute_one_time_delete, execute_one_time_edit, execute_one_time_list, execute_plan,
+    execute_prune, execute_recurring_add, execute_recurring_delete, execute_recurring_disable,
+    execute_recurring_edit, execute_recurring_enable, execute_recurring_list,
+    execute_recurring_occurrence, execute_recurring_occurrences, execute_report, execute_risk,
+    execute_scenario_add_one_time, execute_scenario_add_recurring, execute_scenario_change_amount,
+    execute_scenario_create, execute_scenario_delete, execute_scenario_drop_change,
+    execute_scenario_list, execute_scenario_remove, execute_scenario_show, execute_serve,
+    execute_subscriptions, execute_topup,
 };
+use cashflow::config;
+use cashflow::projection::CHECK_ERROR_EXIT_CODE;
 use clap::Parser;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if let Some(profile) = &cli.profile {
+        config::set_profile_override(profile.clone());
+    }
+
     match &cli.command {
         None => {
-            execute_plan(30, false).await?;
+            execute_plan(30, false, None).await?;
+        }
+        Some(Commands::Plan {
+            days,
+            past,
+            scenario,
+        }) => {
+            execute_plan(*days, *past, scenario.as_deref()).await?;
         }
-        Some(Commands::Plan { days, past }) => {
-            execute_plan(*days, *past).await?;
+
+        Some(Commands::Topup {
+            days,
+            threshold,
+            monthly,
+            create,
+            description,
+        }) => {
+            execute_topup(*days, threshold.as_deref(), *monthly, *create, description).await?;
+        }
+
+        Some(Commands::Risk {
+            days,
+            runs,
+            jitter,
+            seed,
+            threshold,
+        }) => {
+            execute_risk(*days, *runs, *jitter, *seed, threshold.as_deref()).await?;
+        }
+
+        Some(Commands::Report {
+            months,
+            ahead,
+            json,
+        }) => {
+            execute_report(*months, *ahead, *json).await?;
+        }
+
+        Some(Commands::Subscriptions) => {
+            execute_subscriptions().await?;
+        }
+
+        Some(Commands::Add) => {
+            execute_add().await?;
         }
 
         Some(Commands::Balance { action }) => match action {
-            BalanceAction::Set { amount, date } => {
-                execute_balance_set(amount, date.as_deref()).await?;
+            BalanceAction::Set {
+                amount,
+                date,
+                before,
+            } => {
+                execute_balance_set(amount, date.as_deref(), *before).await?;
             }
             BalanceAction::Show => {
                 execute_balance_show().await?;
             }
+            BalanceAction::List { archived } => {
+                execute_balance_list(*archived).await?;
+            }
+            BalanceAction::Delete { target } => {
+                execute_balance_delete(target).await?;
+            }
+            BalanceAction::Diff => {
+                execute_balance_diff().await?;
+            }
+        },
+
+        Some(Commands::Account { action }) => match action {
+            AccountAction::Show => {
+                execute_account_show().await?;
+            }
+            AccountAction::Set {
+                interest_rate,
+                overdraft_rate,
+                interest_day,
+                fee,
+                fee_day,
+            } => {
+                execute_account_set(
+                    interest_rate.as_deref(),
+                    overdraft_rate.as_deref(),
+                    *interest_day,
+                    fee.as_deref(),
+                    *fee_day,
+                )
+                .await?;
+            }
+        },
+
+        Some(Commands::Budget { action }) => match action {
+            BudgetAction::Show { periods } => {
+                execute_budget_show(*periods).await?;
+            }
+            BudgetAction::Set {
+                category,
+                limit,
+                period,
+            } => {
+                execute_budget_set(category, limit, period).await?;
+            }
+            BudgetAction::Delete { category, period } => {
+                execute_budget_delete(category, period.as_deref()).await?;
+            }
+        },
+
+        Some(Commands::Goals { action }) => match action {
+            None | Some(GoalAction::List) => {
+                execute_goals_list().await?;
+            }
+            Some(GoalAction::Add {
+                name,
+                target,
+                date,
+                earmarked,
+            }) => {
+                execute_goal_add(name, target, date, earmarked.as_deref()).await?;
+            }
+            Some(GoalAction::Edit {
+                name,
+                target,
+                date,
+                earmarked,
+            }) => {
+                execute_goal_edit(
+                    name,
+                    target.as_deref(),
+                    date.as_deref(),
+                    earmarked.as_deref(),
+                )
+                .await?;
+            }
+            Some(GoalAction::Fund { name, day }) => {
+                execute_goal_fund(name, *day).await?;
+            }
+            Some(GoalAction::Delete { name }) => {
+                execute_goal_delete(name).await?;
+            }
         },
 
         Some(Commands::Recurring { action }) => match action {
@@ -35,8 +180,19 @@ async fn main() -> Result<()> {
                 description,
                 amount,
                 day,
+                min,
+                max,
+                category,
             } => {
-                execute_recurring_add(description, amount, *day).await?;
+                execute_recurring_add(
+                    description,
+                    amount,
+                    *day,
+                    min.as_deref(),
+                    max.as_deref(),
+                    category.as_deref(),
+                )
+                .await?;
             }
             RecurringAction::List => {
                 execute_recurring_list().await?;
@@ -44,10 +200,27 @@ async fn main() -> Result<()> {
             RecurringAction::Edit {
                 id,
                 amount,
+                effective,
+                rewrite,
                 day,
                 description,
+                min,
+                max,
+                no_range,
+                category,
             } => {
-                execute_recurring_edit(id, amount.as_deref(), *day, description.as_deref()).await?;
+                let edit = RecurringEdit {
+                    amount: amount.as_deref(),
+                    effective: effective.as_deref(),
+                    rewrite: *rewrite,
+                    day: *day,
+                    description: description.as_deref(),
+                    min: min.as_deref(),
+                    max: max.as_deref(),
+                    no_range: *no_range,
+                    category: category.as_deref(),
+                };
+                execute_recurring_edit(id, &edit).await?;
             }
             RecurringAction::Disable { id } => {
                 execute_recurring_disable(id).await?;
@@ -58,6 +231,52 @@ async fn main() -> Result<()> {
             RecurringAction::Delete { id } => {
                 execute_recurring_delete(id).await?;
             }
+            RecurringAction::Skip { id, date } => {
+                execute_recurring_occurrence(id, date.as_deref(), OccurrenceEdit::Skip).await?;
+            }
+            RecurringAction::Move { id, date, to } => {
+                execute_recurring_occurrence(id, date.as_deref(), OccurrenceEdit::Move { to })
+                    .await?;
+            }
+            RecurringAction::Adjust { id, date, amount } => {
+                execute_recurring_occurrence(id, date.as_deref(), OccurrenceEdit::Amount(amount))
+                    .await?;
+            }
+            RecurringAction::Paid { id, date } => {
+                execute_recurring_occurrence(id, date.as_deref(), OccurrenceEdit::Paid).await?;
+            }
+            RecurringAction::Reset { id, date } => {
+                execute_recurring_occurrence(id, date.as_deref(), OccurrenceEdit::Reset).await?;
+            }
+            RecurringAction::Occurrences { id, months } => {
+                execute_recurring_occurrences(id, *months).await?;
+            }
+        },
+
+        Some(Commands::Loan { action }) => match action {
+            LoanAction::Add {
+                description,
+                principal,
+                rate,
+                installments,
+                amount,
+                day,
+                first,
+            } => {
+                execute_loan_add(
+                    description,
+                    principal.as_deref(),
+                    rate,
+                    *installments,
+                    amount.as_deref(),
+                    *day,
+                    first.as_deref(),
+                )
+                .await?;
+            }
+            LoanAction::List => {
+                execute_loan_list().await?;
+            }
         },
 
         Some(Commands::OneTime { action }) => match action {
@@ -65,23 +284,26 @@ async fn main() -> Result<()> {
                 description,
                 amount,
                 date,
+                category,
             } => {
-                execute_one_time_add(description, amount, date).await?;
+                execute_one_time_add(description, amount, date, category.as_deref()).await?;
             }
-            OneTimeAction::List { upcoming } => {
-                execute_one_time_list(*upcoming).await?;
+            OneTimeAction::List { upcoming, archived } => {
+                execute_one_time_list(*upcoming, *archived).await?;
             }
             OneTimeAction::Edit {
                 id,
                 amount,
                 date,
                 description,
+                category,
             } => {
                 execute_one_time_edit(
                     id,
                     amount.as_deref(),
                     date.as_deref(),
                     description.as_deref(),
+                    category.as_deref(),
                 )
                 .await?;
             }
@@ -90,10 +312,101 @@ async fn main() -> Result<()> {
             }
         },
 
+        Some(Commands::Scenario { action }) => match action {
+            ScenarioAction::Create { name } => {
+                execute_scenario_create(name).await?;
+            }
+            ScenarioAction::List => {
+                execute_scenario_list().await?;
+            }
+            ScenarioAction::Show { name } => {
+                execute_scenario_show(name).await?;
+            }
+            ScenarioAction::Delete { name } => {
+                execute_scenario_delete(name).await?;
+            }
+            ScenarioAction::AddRecurring {
+                name,
+                description,
+                amount,
+                day,
+            } => {
+                execute_scenario_add_recurring(name, description, amount, *day).await?;
+            }
+            ScenarioAction::AddOneTime {
+                name,
+                description,
+                amount,
+                date,
+            } => {
+                execute_scenario_add_one_time(name, description, amount, date).await?;
+            }
+            ScenarioAction::Remove {
+                name,
+                id,
+                effective,
+            } => {
+                execute_scenario_remove(name, id, effective.as_deref()).await?;
+            }
+            ScenarioAction::ChangeAmount {
+                name,
+                id,
+                amount,
+                effective,
+            } => {
+                execute_scenario_change_amount(name, id, amount, effective.as_deref()).await?;
+            }
+            ScenarioAction::DropChange { name, number } => {
+                execute_scenario_drop_change(name, *number).await?;
+            }
+        },
+
+        Some(Commands::Check {
+            days,
+            threshold,
+            large_debit,
+            stale_days,
+            no_notify,
+        }) => {
+            let result = execute_check(
+                *days,
+                threshold.as_deref(),
+                large_debit.as_deref(),
+                *stale_days,
+                !*no_notify,
+            )
+            .await;
+            // Errors get their own exit code so monitoring doesn't mistake them for a warning
+            let exit_code = match result {
+                Ok(status) => status.exit_code(),
+                Err(e) => {
+                    eprintln!("Error: {:?}", e);
+                    CHECK_ERROR_EXIT_CODE
+                }
+            };
+            std::process::exit(exit_code);
+        }
+
+        Some(Commands::Metrics { days }) => {
+            execute_metrics(*days).await?;
+        }
+
+        Some(Commands::Serve { bind, token }) => {
+            execute_serve(bind, token.as_deref()).await?;
+        }
+
         Some(Commands::Export { format }) => {
             execute_export(format).await?;
         }
 
+        Some(Commands::Prune { before, dry_run }) => {
+            execute_prune(before.as_deref(), *dry_run).await?;
+        }
+
+        Some(Commands::Doctor { fix }) => {
+            execute_doctor(*fix).await?;
+        }
+
         Some(Commands::Config { action }) => match action {
             ConfigAction::Show => {
                 execute_config_show().await?;
@@ -101,6 +414,36 @@ async fn main() -> Result<()> {
             ConfigAction::SetDataDir { path } => {
                 execute_config_set_data_dir(path).await?;
             }
+            ConfigAction::SetAutoPrune { days } => {
+                execute_config_set_auto_prune(days).await?;
+            }
+            ConfigAction::Profile { action } => match action {
+                ProfileAction::Add {
+                    name,
+                    data_dir,
+                    warning_threshold,
+                    show_past,
+                    stale_snapshot_days,
+                } => {
+                    execute_config_profile_add(
+                        name,
+                        data_dir,
+                        warning_threshold.as_deref(),
+                        *show_past,
+                        *stale_snapshot_days,
+                    )
+                    .await?;
+                }
+                ProfileAction::List => {
+                    execute_config_profile_list().await?;
+                }
+                ProfileAction::Use { name } => {
+                    execute_config_profile_use(name).await?;
+                }
+                ProfileAction::Remove { name } => {
+                    execute_config_profile_remove(name).await?;
+                }
+            },
         },
     }
 
@@ -1,3 +1,20 @@
+mod account;
+mod budget;
 mod data;
+mod goal;
+mod installment;
+mod occurrence;
+mod scenario;
+mod text;
+mod validation;
 
+pub use account::*;
+pub use budget::*;
 pub use data::*;
+pub use goal::*;
+pub use installment::*;
+pub use occurrence::*;
+pub use scenario::*;
+pub use validation::*;
+
+pub(crate) use text::normalize_text;
@@ -0,0 +1,37 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Interest and fee rules of the bank account, applied by the projection
+/// All rates are annual percentages; zero disables the rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccountRules {
+    /// Interest paid on positive balances (e.g. 3.5)
+    pub interest_rate: Decimal,
+    /// Interest charged on negative balances (e.g. 19.9)
+    pub overdraft_rate: Decimal,
+    /// Day of month when accrued interest is credited or charged (31 = last day)
+    pub interest_day: u8,
+    /// Monthly account fee as a positive number
+    pub monthly_fee: Decimal,
+    /// Day of month when the fee is charged
+    pub fee_day: u8,
+}
+
+impl Default for AccountRules {
+    fn default() -> Self {
+        Self {
+            interest_rate: Decimal::ZERO,
+            overdraft_rate: Decimal::ZERO,
+            interest_day: 31,
+            monthly_fee: Decimal::ZERO,
+            fee_day: 1,
+        }
+    }
+}
+
+impl AccountRules {
+    /// True if no rule is set and the projection is unaffected
+    pub fn is_empty(&self) -> bool {
+        self.interest_rate.is_zero() && self.overdraft_rate.is_zero() && self.monthly_fee.is_zero()
+    }
+}
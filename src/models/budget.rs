@@ -0,0 +1,95 @@
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Spending limit of a category over a calendar period
+/// Example: at most 8 000 Kč a month on "Jídlo"
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Budget {
+    pub category: String,
+    pub period: BudgetPeriod,
+    /// Maximum spending per period as a positive number
+    pub limit: Decimal,
+}
+
+impl Budget {
+    /// True if a transaction with the given category counts against this budget
+    /// Categories are compared case-insensitively
+    pub fn matches(&self, category: Option<&str>) -> bool {
+        category.is_some_and(|c| c.to_lowercase() == self.category.to_lowercase())
+    }
+}
+
+/// Calendar period a budget limit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BudgetPeriod {
+    Monthly,
+    Yearly,
+}
+
+impl BudgetPeriod {
+    /// First day of the period containing `date`
+    pub fn start(&self, date: NaiveDate) -> NaiveDate {
+        let month = match self {
+            BudgetPeriod::Monthly => date.month(),
+            BudgetPeriod::Yearly => 1,
+        };
+        NaiveDate::from_ymd_opt(date.year(), month, 1).unwrap_or(date)
+    }
+
+    /// Last day of the period containing `date`
+    pub fn end(&self, date: NaiveDate) -> NaiveDate {
+        self.next(date) - Duration::days(1)
+    }
+
+    /// First day of the period following the one containing `date`
+    pub fn next(&self, date: NaiveDate) -> NaiveDate {
+        let length = match self {
+            BudgetPeriod::Monthly => Months::new(1),
+            BudgetPeriod::Yearly => Months::new(12),
+        };
+        self.start(date) + length
+    }
+}
+
+impl fmt::Display for BudgetPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BudgetPeriod::Monthly => write!(f, "monthly"),
+            BudgetPeriod::Yearly => write!(f, "yearly"),
+        }
+    }
+}
+
+impl FromStr for BudgetPeriod {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "monthly" | "month" | "m" => Ok(BudgetPeriod::Monthly),
+            "yearly" | "year" | "y" => Ok(BudgetPeriod::Yearly),
+            _ => anyhow::bail!("Unknown budget period '{}'. Use monthly or yearly.", s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_period_bounds() {
+        let day = date(2024, 2, 14);
+        assert_eq!(BudgetPeriod::Monthly.start(day), date(2024, 2, 1));
+        assert_eq!(BudgetPeriod::Monthly.end(day), date(2024, 2, 29));
+        assert_eq!(BudgetPeriod::Yearly.start(day), date(2024, 1, 1));
+        assert_eq!(BudgetPeriod::Yearly.end(day), date(2024, 12, 31));
+        assert_eq!(BudgetPeriod::Yearly.next(day), date(2025, 1, 1));
+    }
+}
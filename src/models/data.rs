@@ -1,23 +1,47 @@
-use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use crate::models::{
+    AccountRules, Budget, Goal, InstallmentPlan, OccurrenceOverride, OccurrenceStatus, Scenario,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+/// Current version of the persisted data format
+/// Bump this together with a new migration step in `storage::migrations`
+pub const SCHEMA_VERSION: u32 = 12;
+
 /// Top-level data structure persisted in RON format
-/// This is the source of truth stored in ~/.cashflow/data.ron
+/// This is the source of truth stored in $XDG_DATA_HOME/cashflow/data.ron
+/// Older files are upgraded by `storage::migrations` based on `schema_version`, which
+/// inserts every field added since
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CashflowData {
+    /// Version of the persisted format, used to migrate older files on load
+    pub schema_version: u32,
     pub recurring: Vec<RecurringTransaction>,
     pub one_time: Vec<OneTimeTransaction>,
     pub balance_snapshots: Vec<BalanceSnapshot>,
+    /// What-if overlays, never applied to the real plan unless requested
+    pub scenarios: Vec<Scenario>,
+    /// Interest and fee rules of the account
+    pub account: AccountRules,
+    /// Spending limits per category
+    pub budgets: Vec<Budget>,
+    /// Savings goals with money earmarked in the account
+    pub goals: Vec<Goal>,
 }
 
 impl Default for CashflowData {
     fn default() -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             recurring: Vec::new(),
             one_time: Vec::new(),
             balance_snapshots: Vec::new(),
+            scenarios: Vec::new(),
+            account: AccountRules::default(),
+            budgets: Vec::new(),
+            goals: Vec::new(),
         }
     }
 }
@@ -29,12 +53,69 @@ pub struct RecurringTransaction {
     pub id: Uuid,
     pub description: String,
     /// Positive for income, negative for expenses
+    /// Amount before the first entry of `amount_changes` takes effect
     pub amount: Decimal,
     /// Day of month when payment occurs (1-31)
     pub day_of_month: u8,
     /// Inactive transactions are not projected but kept for history
     pub active: bool,
     pub created_at: DateTime<Utc>,
+    /// Price changes sorted by effective date, e.g. a Netflix price increase
+    pub amount_changes: Vec<AmountChange>,
+    /// Loan repayment schedule; occurrences stop once the loan is paid off
+    pub installment_plan: Option<InstallmentPlan>,
+    /// Range of a variable amount (utilities, card bills); `amount` is the expected value
+    pub amount_range: Option<AmountRange>,
+    /// Optional category used by reports, e.g. "Bydlení"
+    pub category: Option<String>,
+    /// Last day the transaction may occur, e.g. the target date of a savings goal
+    pub end_date: Option<NaiveDate>,
+    /// Skipped, moved, changed or paid single occurrences, sorted by scheduled date
+    pub overrides: Vec<OccurrenceOverride>,
+}
+
+/// Lowest and highest amount a variable recurring transaction is expected to take
+/// For expenses `min` is the largest payment, for income the smallest one
+/// The range belongs to the transaction's base `amount` and scales with its amount changes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmountRange {
+    pub min: Decimal,
+    pub max: Decimal,
+}
+
+impl AmountRange {
+    /// Creates a range from two bounds given in any order
+    pub fn new(a: Decimal, b: Decimal) -> Self {
+        Self {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// True if the range contains the amount and lies on the same side of zero
+    pub fn fits(&self, amount: Decimal) -> bool {
+        let same_sign = if amount.is_sign_negative() {
+            self.max <= Decimal::ZERO
+        } else {
+            self.min >= Decimal::ZERO
+        };
+        same_sign && self.min <= amount && amount <= self.max
+    }
+
+    /// Range multiplied by a factor, e.g. after a price change
+    pub fn scaled(&self, factor: Decimal) -> Self {
+        Self::new(
+            (self.min * factor).round_dp(2),
+            (self.max * factor).round_dp(2),
+        )
+    }
+}
+
+/// New amount of a recurring transaction from a given date on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmountChange {
+    pub effective_from: NaiveDate,
+    pub amount: Decimal,
 }
 
 impl RecurringTransaction {
@@ -46,12 +127,131 @@ impl RecurringTransaction {
             day_of_month,
             active: true,
             created_at: Utc::now(),
+            amount_changes: Vec::new(),
+            installment_plan: None,
+            amount_range: None,
+            category: None,
+            end_date: None,
+            overrides: Vec::new(),
         }
     }
+
+    /// False for dates outside a loan's repayment schedule or after the end date
+    pub fn is_scheduled_in(&self, date: NaiveDate) -> bool {
+        self.end_date.is_none_or(|end| date <= end)
+            && self
+                .installment_plan
+                .as_ref()
+                .is_none_or(|plan| plan.installment_index(date).is_some())
+    }
+
+    /// Amount of the occurrence on the given date
+    pub fn amount_on(&self, date: NaiveDate) -> Decimal {
+        self.amount_changes
+            .iter()
+            .rev()
+            .find(|c| c.effective_from <= date)
+            .map_or(self.amount, |c| c.amount)
+    }
+
+    /// Amount range of the occurrence on the given date, scaled by the amount change in
+    /// effect, e.g. a 10 % price increase widens the range by 10 % too
+    pub fn range_on(&self, date: NaiveDate) -> Option<AmountRange> {
+        let range = self.amount_range.as_ref()?;
+        let amount = self.amount_on(date);
+        if amount == self.amount || self.amount.is_zero() {
+            return Some(range.clone());
+        }
+        Some(range.scaled(amount / self.amount))
+    }
+
+    /// Sets the amount range for the amount in effect on the given date
+    pub fn set_range_on(&mut self, date: NaiveDate, range: AmountRange) {
+        let amount = self.amount_on(date);
+        self.amount_range = if amount == self.amount || amount.is_zero() {
+            Some(range)
+        } else {
+            Some(range.scaled(self.amount / amount))
+        };
+    }
+
+    /// Pessimistic amount of the occurrence on the given date: the low end of the range
+    pub fn worst_amount_on(&self, date: NaiveDate) -> Decimal {
+        self.range_on(date)
+            .map_or_else(|| self.amount_on(date), |range| range.min)
+    }
+
+    /// Override of the occurrence scheduled on `date`
+    pub fn occurrence_override(&self, date: NaiveDate) -> Option<&OccurrenceOverride> {
+        self.overrides.iter().find(|o| o.date == date)
+    }
+
+    /// Override of the occurrence that actually happens on `date` (after moves),
+    /// skipped occurrences are ignored
+    pub fn override_on(&self, date: NaiveDate) -> Option<&OccurrenceOverride> {
+        self.overrides
+            .iter()
+            .find(|o| o.actual_date() == date && o.status != OccurrenceStatus::Skipped)
+    }
+
+    /// Amount of the occurrence scheduled on `date`, including a one-off change
+    pub fn occurrence_amount(&self, date: NaiveDate) -> Decimal {
+        self.occurrence_override(date)
+            .and_then(|o| o.amount)
+            .unwrap_or_else(|| self.amount_on(date))
+    }
+
+    /// Changes the occurrence scheduled on `date`, the override is dropped again
+    /// when it ends up changing nothing
+    pub fn update_occurrence(
+        &mut self,
+        date: NaiveDate,
+        change: impl FnOnce(&mut OccurrenceOverride),
+    ) {
+        let index = match self.overrides.iter().position(|o| o.date == date) {
+            Some(index) => index,
+            None => {
+                self.overrides.push(OccurrenceOverride::new(date));
+                self.overrides.len() - 1
+            }
+        };
+        change(&mut self.overrides[index]);
+        self.overrides.retain(|o| !o.is_noop());
+        self.overrides.sort_by_key(|o| o.date);
+    }
+
+    /// First amount change after the given date, if one is scheduled
+    pub fn next_amount_change(&self, date: NaiveDate) -> Option<&AmountChange> {
+        self.amount_changes.iter().find(|c| c.effective_from > date)
+    }
+
+    /// Schedules a new amount from the given date, replacing a change on the same date
+    pub fn schedule_amount(&mut self, effective_from: NaiveDate, amount: Decimal) {
+        self.amount_changes
+            .retain(|c| c.effective_from != effective_from);
+        self.amount_changes.push(AmountChange {
+            effective_from,
+            amount,
+        });
+        self.amount_changes.sort_by_key(|c| c.effective_from);
+    }
+
+    /// Replaces the amount for all occurrences, past and future, dropping the history
+    /// An amount range is scaled along
+    pub fn set_amount(&mut self, amount: Decimal) {
+        if !self.amount.is_zero() {
+            self.amount_range = self
+                .amount_range
+                .as_ref()
+                .map(|range| range.scaled(amount / self.amount));
+        }
+        self.amount = amount;
+        self.amount_changes.clear();
+    }
 }
 
 /// One-time transaction (e.g., "Převod z Air Bank")
-/// These are not recurring and should be pruned after they're in the past
+/// These are not recurring; past ones are moved to archive.ron by `cashflow prune`
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OneTimeTransaction {
     pub id: Uuid,
@@ -59,6 +259,8 @@ pub struct OneTimeTransaction {
     pub amount: Decimal,
     pub date: NaiveDate,
     pub created_at: DateTime<Utc>,
+    /// Optional category used by reports
+    pub category: Option<String>,
 }
 
 impl OneTimeTransaction {
@@ -69,6 +271,7 @@ impl OneTimeTransaction {
             amount,
             date,
             created_at: Utc::now(),
+            category: None,
         }
     }
 }
@@ -80,32 +283,64 @@ pub struct BalanceSnapshot {
     pub id: Uuid,
     pub date: NaiveDate,
     pub balance: Decimal,
+    /// Whether the transactions on `date` are already reflected in `balance`
+    pub timing: SnapshotTiming,
     pub created_at: DateTime<Utc>,
 }
 
+/// When during its day a balance snapshot was taken
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotTiming {
+    /// Taken before the day's transactions, they are still applied to the balance
+    Before,
+    /// Taken at the end of the day, the day's transactions are already included
+    #[default]
+    After,
+}
+
 impl BalanceSnapshot {
     pub fn new(date: NaiveDate, balance: Decimal) -> Self {
         Self {
             id: Uuid::new_v4(),
             date,
             balance,
+            timing: SnapshotTiming::default(),
             created_at: Utc::now(),
         }
     }
+
+    /// First day whose transactions are not reflected in the balance yet
+    pub fn first_open_day(&self) -> NaiveDate {
+        match self.timing {
+            SnapshotTiming::Before => self.date,
+            SnapshotTiming::After => self.date + Duration::days(1),
+        }
+    }
 }
 
 /// Transaction view for display (not persisted)
 /// Generated from recurring templates or one-time transactions
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TransactionView {
+    /// ID of the recurring template or one-time transaction this row comes from
+    pub source_id: Uuid,
     pub date: NaiveDate,
     pub day_of_month: u8,
     pub description: String,
     pub amount: Decimal,
+    pub category: Option<String>,
     /// True if from one_time, false if from recurring template
     pub is_one_time: bool,
+    /// True for interest and fees generated from the account rules
+    pub is_generated: bool,
     /// Calculated running balance after this transaction
     pub balance_after: Decimal,
+    /// Pessimistic amount, differs from `amount` only for variable amounts
+    pub worst_amount: Decimal,
+    /// Running balance if every variable amount hits its worst case
+    pub worst_balance_after: Decimal,
+    /// True if the occurrence was marked as paid
+    pub confirmed: bool,
 }
 
 impl TransactionView {
@@ -114,24 +349,60 @@ impl TransactionView {
         date: NaiveDate,
         balance_after: Decimal,
     ) -> Self {
+        // A moved occurrence keeps the amount and installment number of its scheduled date
+        let occurrence = txn.override_on(date);
+        let scheduled = occurrence.map_or(date, |o| o.date);
+        let amount = txn.occurrence_amount(scheduled);
+
+        // Loan installments show their position, e.g. "Hypotéka (13/240)"
+        let description = match txn
+            .installment_plan
+            .as_ref()
+            .and_then(|plan| Some((plan.installment_index(scheduled)?, plan.installments)))
+        {
+            Some((index, count)) => format!("{} ({}/{})", txn.description, index + 1, count),
+            None => txn.description.clone(),
+        };
+
         Self {
+            source_id: txn.id,
             date,
             day_of_month: txn.day_of_month,
-            description: txn.description.clone(),
-            amount: txn.amount,
+            description,
+            amount,
+            category: txn.category.clone(),
             is_one_time: false,
+            is_generated: false,
             balance_after,
+            worst_amount: amount,
+            worst_balance_after: balance_after,
+            confirmed: occurrence.is_some_and(|o| o.status == OccurrenceStatus::Confirmed),
         }
     }
 
     pub fn from_one_time(txn: &OneTimeTransaction, balance_after: Decimal) -> Self {
         Self {
+            source_id: txn.id,
             date: txn.date,
             day_of_month: txn.date.day() as u8,
             description: txn.description.clone(),
             amount: txn.amount,
+            category: txn.category.clone(),
             is_one_time: true,
+            is_generated: false,
             balance_after,
+            worst_amount: txn.amount,
+            worst_balance_after: balance_after,
+            confirmed: false,
+        }
+    }
+
+    /// Row generated from the account rules (interest, fees)
+    pub fn generated(txn: &OneTimeTransaction, balance_after: Decimal) -> Self {
+        Self {
+            is_one_time: false,
+            is_generated: true,
+            ..Self::from_one_time(txn, balance_after)
         }
     }
 }
@@ -0,0 +1,41 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Something to save for by a date, e.g. a holiday or the yearly car insurance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Goal {
+    pub name: String,
+    /// Amount needed by the target date, as a positive number
+    pub target: Decimal,
+    pub target_date: NaiveDate,
+    /// Money already set aside for the goal in the account; not available for spending
+    /// until the target date
+    pub earmarked: Decimal,
+    /// Recurring transfer funding the goal, created by `goals fund`
+    pub contribution_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Goal {
+    pub fn new(name: String, target: Decimal, target_date: NaiveDate) -> Self {
+        Self {
+            name,
+            target,
+            target_date,
+            earmarked: Decimal::ZERO,
+            contribution_id: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Money reserved for the goal on the given date; released after the target date
+    pub fn earmarked_on(&self, date: NaiveDate) -> Decimal {
+        if date <= self.target_date {
+            self.earmarked
+        } else {
+            Decimal::ZERO
+        }
+    }
+}
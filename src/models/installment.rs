@@ -0,0 +1,217 @@
+use anyhow::Context;
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Highest annual interest rate in percent a loan may have
+pub const MAX_ANNUAL_RATE: Decimal = Decimal::ONE_HUNDRED;
+
+/// Most installments a loan may have (50 years of monthly payments)
+pub const MAX_INSTALLMENTS: u32 = 600;
+
+/// Repayment schedule of a loan paid by a recurring transaction
+/// The recurring transaction's amount is the (negative) monthly installment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallmentPlan {
+    /// Outstanding principal before the first installment, None if only the count is known
+    pub principal: Option<Decimal>,
+    /// Annual interest rate in percent (e.g. 4.5)
+    pub annual_rate: Decimal,
+    /// Number of installments left at `first_payment`
+    pub installments: u32,
+    /// Month of the first installment covered by the plan
+    pub first_payment: NaiveDate,
+}
+
+impl InstallmentPlan {
+    /// Computes the fixed monthly annuity payment for a principal, annual rate and term
+    /// Fails for a rate or term outside the supported bounds, or a payment too large to compute
+    pub fn annuity_payment(
+        principal: Decimal,
+        annual_rate: Decimal,
+        installments: u32,
+    ) -> anyhow::Result<Decimal> {
+        Self::check_terms(annual_rate, installments)?;
+
+        let rate = monthly_rate(annual_rate);
+        if rate.is_zero() {
+            return Ok((principal / Decimal::from(installments)).round_dp(2));
+        }
+
+        // payment = P * r / (1 - (1 + r)^-n)
+        let payment = (0..installments)
+            .try_fold(Decimal::ONE, |acc, _| acc.checked_mul(Decimal::ONE + rate))
+            .and_then(|growth| {
+                principal
+                    .checked_mul(rate)?
+                    .checked_mul(growth)?
+                    .checked_div(growth - Decimal::ONE)
+            })
+            .context("Installment amount is too large, check the principal")?;
+        Ok(payment.round_dp(2))
+    }
+
+    /// Checks the annual rate and number of installments of a loan against the supported bounds
+    pub fn check_terms(annual_rate: Decimal, installments: u32) -> anyhow::Result<()> {
+        if installments == 0 || installments > MAX_INSTALLMENTS {
+            anyhow::bail!(
+                "Number of installments must be between 1 and {}",
+                MAX_INSTALLMENTS
+            );
+        }
+        if annual_rate.is_sign_negative() || annual_rate > MAX_ANNUAL_RATE {
+            anyhow::bail!("Interest rate must be between 0 and {} %", MAX_ANNUAL_RATE);
+        }
+        Ok(())
+    }
+
+    /// True if the rate and number of installments are within the supported bounds
+    pub fn has_valid_terms(&self) -> bool {
+        Self::check_terms(self.annual_rate, self.installments).is_ok()
+    }
+
+    /// Zero-based index of the installment due in the month of the given date,
+    /// None if the date is before the first or after the last installment
+    pub fn installment_index(&self, date: NaiveDate) -> Option<u32> {
+        let index = month_number(date) - month_number(self.first_payment);
+        if index >= 0 && (index as u32) < self.installments {
+            Some(index as u32)
+        } else {
+            None
+        }
+    }
+
+    /// Date of the last installment, for a payment on the given day of month
+    pub fn last_payment(&self, day_of_month: u8) -> NaiveDate {
+        payment_date(
+            self.first_payment,
+            self.installments.saturating_sub(1),
+            day_of_month,
+        )
+    }
+
+    /// Number of installments paid strictly before the given date
+    pub fn paid_before(&self, date: NaiveDate, day_of_month: u8) -> u32 {
+        (0..self.installments)
+            .take_while(|&i| payment_date(self.first_payment, i, day_of_month) < date)
+            .count() as u32
+    }
+
+    /// Outstanding principal after the given number of installments of `payment` (positive)
+    /// Without a known principal this is the sum of the remaining installments
+    pub fn remaining_principal(&self, payment: Decimal, paid: u32) -> Decimal {
+        let paid = paid.min(self.installments);
+        let Some(principal) = self.principal else {
+            return payment.saturating_mul(Decimal::from(self.installments - paid));
+        };
+        if paid == self.installments {
+            return Decimal::ZERO;
+        }
+
+        // An installment below the interest makes the debt grow, possibly beyond Decimal
+        let rate = monthly_rate(self.annual_rate);
+        let remaining = (0..paid).try_fold(principal, |balance, _| {
+            balance
+                .checked_add(balance.checked_mul(rate)?.round_dp(2))?
+                .checked_sub(payment)
+        });
+        remaining.unwrap_or(Decimal::MAX).max(Decimal::ZERO)
+    }
+}
+
+fn monthly_rate(annual_rate: Decimal) -> Decimal {
+    annual_rate / Decimal::from(1200)
+}
+
+/// Months since year 0, used to count installments across year boundaries
+fn month_number(date: NaiveDate) -> i64 {
+    date.year() as i64 * 12 + date.month0() as i64
+}
+
+/// Date of the installment `index` months after the first one,
+/// with the day clamped to the end of shorter months
+fn payment_date(first_payment: NaiveDate, index: u32, day_of_month: u8) -> NaiveDate {
+    let month = month_number(first_payment) + index as i64;
+    let (year, month) = ((month / 12) as i32, (month % 12) as u32 + 1);
+
+    (1..=day_of_month as u32)
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .unwrap_or(first_payment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_annuity_payment() {
+        // 100 000 at 12 % p.a. over 12 months
+        let payment =
+            InstallmentPlan::annuity_payment(Decimal::from(100000), Decimal::from(12), 12).unwrap();
+        assert_eq!(payment, Decimal::from_str("8884.88").unwrap());
+
+        let payment =
+            InstallmentPlan::annuity_payment(Decimal::from(12000), Decimal::ZERO, 12).unwrap();
+        assert_eq!(payment, Decimal::from(1000));
+    }
+
+    #[test]
+    fn test_annuity_payment_bounds() {
+        let principal = Decimal::from(1_000_000);
+
+        // Highest rate over the longest term stays computable
+        assert!(
+            InstallmentPlan::annuity_payment(principal, MAX_ANNUAL_RATE, MAX_INSTALLMENTS).is_ok()
+        );
+        assert!(InstallmentPlan::annuity_payment(principal, Decimal::from(300), 360).is_err());
+        assert!(InstallmentPlan::annuity_payment(principal, Decimal::from(5), 0).is_err());
+        assert!(InstallmentPlan::annuity_payment(principal, Decimal::from(5), 601).is_err());
+        assert!(InstallmentPlan::annuity_payment(principal, Decimal::from(-1), 12).is_err());
+        assert!(InstallmentPlan::annuity_payment(Decimal::MAX, MAX_ANNUAL_RATE, 1).is_err());
+
+        // An installment below the interest never pays the loan off, the debt outgrows Decimal
+        let plan = InstallmentPlan {
+            principal: Some(Decimal::from(1_000_000_000)),
+            annual_rate: MAX_ANNUAL_RATE,
+            installments: MAX_INSTALLMENTS,
+            first_payment: date(2025, 1, 1),
+        };
+        assert_eq!(
+            plan.remaining_principal(Decimal::ONE, MAX_INSTALLMENTS - 1),
+            Decimal::MAX
+        );
+    }
+
+    #[test]
+    fn test_schedule() {
+        let plan = InstallmentPlan {
+            principal: Some(Decimal::from(100000)),
+            annual_rate: Decimal::from(12),
+            installments: 12,
+            first_payment: date(2025, 11, 15),
+        };
+        let payment = Decimal::from_str("8884.88").unwrap();
+
+        assert_eq!(plan.installment_index(date(2025, 10, 15)), None);
+        assert_eq!(plan.installment_index(date(2025, 11, 1)), Some(0));
+        assert_eq!(plan.installment_index(date(2026, 10, 15)), Some(11));
+        assert_eq!(plan.installment_index(date(2026, 11, 15)), None);
+        assert_eq!(plan.last_payment(15), date(2026, 10, 15));
+
+        assert_eq!(plan.paid_before(date(2025, 11, 15), 15), 0);
+        assert_eq!(plan.paid_before(date(2025, 11, 16), 15), 1);
+
+        // First installment: 1 000 interest, the rest pays off principal
+        assert_eq!(
+            plan.remaining_principal(payment, 1),
+            Decimal::from_str("92115.12").unwrap()
+        );
+        assert_eq!(plan.remaining_principal(payment, 12), Decimal::ZERO);
+    }
+}
@@ -0,0 +1,61 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// State of a single occurrence of a recurring transaction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OccurrenceStatus {
+    /// Expected, not confirmed yet
+    #[default]
+    Pending,
+    /// Marked as paid
+    Confirmed,
+    /// Does not happen this time
+    Skipped,
+}
+
+impl fmt::Display for OccurrenceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            OccurrenceStatus::Pending => "pending",
+            OccurrenceStatus::Confirmed => "paid",
+            OccurrenceStatus::Skipped => "skipped",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Change to one occurrence of a recurring transaction, keyed by the date the
+/// recurring rule schedules it on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OccurrenceOverride {
+    /// Date the occurrence is scheduled on by the recurring rule
+    pub date: NaiveDate,
+    pub status: OccurrenceStatus,
+    /// Date the occurrence happens on instead
+    pub moved_to: Option<NaiveDate>,
+    /// Amount of this occurrence only, e.g. twice the usual one when paid twice
+    pub amount: Option<Decimal>,
+}
+
+impl OccurrenceOverride {
+    pub fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            status: OccurrenceStatus::Pending,
+            moved_to: None,
+            amount: None,
+        }
+    }
+
+    /// Date the occurrence actually happens on
+    pub fn actual_date(&self) -> NaiveDate {
+        self.moved_to.unwrap_or(self.date)
+    }
+
+    /// True if the override doesn't change anything and can be dropped
+    pub fn is_noop(&self) -> bool {
+        self.status == OccurrenceStatus::Pending && self.moved_to.is_none() && self.amount.is_none()
+    }
+}
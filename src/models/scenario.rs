@@ -0,0 +1,228 @@
+use crate::models::{CashflowData, OneTimeTransaction, RecurringTransaction};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Named what-if scenario stored alongside the real data
+/// Example: "new lease" with rent 2 000 higher and Netflix cancelled
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    /// Changes applied in order on top of the real data
+    pub changes: Vec<ScenarioChange>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Single change a scenario makes to the real data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ScenarioChange {
+    /// Adds a recurring transaction that only exists in the scenario
+    AddRecurring(RecurringTransaction),
+    /// Adds a one-time transaction that only exists in the scenario
+    AddOneTime(OneTimeTransaction),
+    /// Removes a recurring or one-time transaction from the given date on
+    /// A recurring transaction ends the day before, earlier one-time transactions stay
+    Remove { id: Uuid, effective_from: NaiveDate },
+    /// Replaces the amount of a recurring or one-time transaction from the given date on
+    /// For recurring transactions this also overrides amount changes scheduled after it
+    ChangeAmount {
+        id: Uuid,
+        amount: Decimal,
+        effective_from: NaiveDate,
+    },
+}
+
+impl Scenario {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            changes: Vec::new(),
+            created_at: Utc::now(),
+        }
+    }
+
+    /// Returns a copy of the data with the scenario's changes applied
+    /// Changes referring to transactions that no longer exist are ignored
+    pub fn apply(&self, data: &CashflowData) -> CashflowData {
+        let mut result = data.clone();
+        result.scenarios.clear();
+
+        for change in &self.changes {
+            match change {
+                ScenarioChange::AddRecurring(txn) => result.recurring.push(txn.clone()),
+                ScenarioChange::AddOneTime(txn) => result.one_time.push(txn.clone()),
+                ScenarioChange::Remove { id, effective_from } => {
+                    let last_day = *effective_from - Duration::days(1);
+                    if let Some(txn) = result.recurring.iter_mut().find(|t| t.id == *id) {
+                        txn.end_date = Some(txn.end_date.map_or(last_day, |end| end.min(last_day)));
+                    }
+                    result
+                        .one_time
+                        .retain(|t| t.id != *id || t.date < *effective_from);
+                }
+                ScenarioChange::ChangeAmount {
+                    id,
+                    amount,
+                    effective_from,
+                } => {
+                    if let Some(txn) = result.recurring.iter_mut().find(|t| t.id == *id) {
+                        txn.amount_changes
+                            .retain(|c| c.effective_from < *effective_from);
+                        txn.schedule_amount(*effective_from, *amount);
+                    }
+                    if let Some(txn) = result
+                        .one_time
+                        .iter_mut()
+                        .find(|t| t.id == *id && t.date >= *effective_from)
+                    {
+                        txn.amount = *amount;
+                    }
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl ScenarioChange {
+    /// Human-readable description, resolving IDs against the real data
+    pub fn describe(&self, data: &CashflowData) -> String {
+        let name_of = |id: &Uuid| {
+            data.recurring
+                .iter()
+                .find(|t| t.id == *id)
+                .map(|t| t.description.clone())
+                .or_else(|| {
+                    data.one_time
+                        .iter()
+                        .find(|t| t.id == *id)
+                        .map(|t| t.description.clone())
+                })
+                .unwrap_or_else(|| format!("{} (missing)", id))
+        };
+
+        match self {
+            ScenarioChange::AddRecurring(txn) => format!(
+                "Add recurring \"{}\" {} on day {}",
+                txn.description, txn.amount, txn.day_of_month
+            ),
+            ScenarioChange::AddOneTime(txn) => format!(
+                "Add one-time \"{}\" {} on {}",
+                txn.description,
+                txn.amount,
+                txn.date.format("%d.%m.%Y")
+            ),
+            ScenarioChange::Remove { id, effective_from } => format!(
+                "Remove \"{}\" from {}",
+                name_of(id),
+                effective_from.format("%d.%m.%Y")
+            ),
+            ScenarioChange::ChangeAmount {
+                id,
+                amount,
+                effective_from,
+            } => format!(
+                "Change amount of \"{}\" to {} from {}",
+                name_of(id),
+                amount,
+                effective_from.format("%d.%m.%Y")
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_apply_scenario() {
+        let mut data = CashflowData::default();
+        let mut rent = RecurringTransaction::new("Rent".to_string(), Decimal::from(-15000), 1);
+        rent.schedule_amount(date(12, 1), Decimal::from(-16000));
+        let netflix = RecurringTransaction::new(
+            "Netflix".to_string(),
+            Decimal::from_str("-478").unwrap(),
+            14,
+        );
+        data.recurring.push(rent.clone());
+        data.recurring.push(netflix.clone());
+
+        let mut scenario = Scenario::new("new lease".to_string());
+        scenario.changes.push(ScenarioChange::ChangeAmount {
+            id: rent.id,
+            amount: Decimal::from(-17000),
+            effective_from: date(11, 1),
+        });
+        scenario.changes.push(ScenarioChange::Remove {
+            id: netflix.id,
+            effective_from: date(11, 1),
+        });
+        scenario
+            .changes
+            .push(ScenarioChange::AddOneTime(OneTimeTransaction::new(
+                "Deposit".to_string(),
+                Decimal::from(-30000),
+                date(11, 1),
+            )));
+
+        let applied = scenario.apply(&data);
+
+        // The new rent applies from its date on and replaces the later scheduled change
+        let rent = &applied.recurring[0];
+        assert_eq!(rent.amount_on(date(10, 1)), Decimal::from(-15000));
+        assert_eq!(rent.amount_on(date(11, 1)), Decimal::from(-17000));
+        assert_eq!(rent.amount_on(date(12, 1)), Decimal::from(-17000));
+
+        // Netflix still runs until the day before the removal
+        let netflix = &applied.recurring[1];
+        assert_eq!(netflix.end_date, Some(date(10, 31)));
+        assert!(netflix.is_scheduled_in(date(10, 14)));
+        assert!(!netflix.is_scheduled_in(date(11, 14)));
+        assert_eq!(applied.one_time.len(), 1);
+
+        // The real data is untouched
+        assert_eq!(
+            data.recurring[0].amount_on(date(11, 1)),
+            Decimal::from(-15000)
+        );
+        assert_eq!(data.recurring[1].end_date, None);
+    }
+
+    #[test]
+    fn test_apply_dated_changes_to_one_time() {
+        let mut data = CashflowData::default();
+        let repair =
+            OneTimeTransaction::new("Oprava".to_string(), Decimal::from(-3000), date(10, 5));
+        data.one_time.push(repair.clone());
+
+        let mut scenario = Scenario::new("later".to_string());
+        scenario.changes.push(ScenarioChange::ChangeAmount {
+            id: repair.id,
+            amount: Decimal::from(-5000),
+            effective_from: date(10, 6),
+        });
+        scenario.changes.push(ScenarioChange::Remove {
+            id: repair.id,
+            effective_from: date(10, 6),
+        });
+
+        // The transaction is dated before both changes, so neither touches it
+        let applied = scenario.apply(&data);
+        assert_eq!(applied.one_time.len(), 1);
+        assert_eq!(applied.one_time[0].amount, Decimal::from(-3000));
+
+        scenario.changes[1] = ScenarioChange::Remove {
+            id: repair.id,
+            effective_from: date(10, 5),
+        };
+        assert!(scenario.apply(&data).one_time.is_empty());
+    }
+}
@@ -0,0 +1,9 @@
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
+
+/// Lowercase without diacritics, so "nájem" matches "Najem"
+pub(crate) fn normalize_text(s: &str) -> String {
+    s.nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
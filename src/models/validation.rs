@@ -0,0 +1,354 @@
+use crate::models::{CashflowData, OneTimeTransaction, RecurringTransaction};
+use chrono::NaiveDate;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+use uuid::Uuid;
+
+/// A problem found in CashflowData (typically caused by hand-editing data.ron)
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ValidationIssue {
+    #[error(
+        "Recurring transaction \"{description}\" has invalid day of month {day} (must be 1-31)"
+    )]
+    InvalidDayOfMonth {
+        id: Uuid,
+        description: String,
+        day: u8,
+    },
+
+    #[error("{kind} transaction \"{description}\" has zero amount")]
+    ZeroAmount {
+        id: Uuid,
+        kind: &'static str,
+        description: String,
+    },
+
+    #[error(
+        "Recurring transaction \"{description}\" has an amount range that does not contain its amount or has the opposite sign"
+    )]
+    InvalidAmountRange { id: Uuid, description: String },
+
+    #[error("Loan \"{description}\" has no installments left in its plan")]
+    EmptyInstallmentPlan { id: Uuid, description: String },
+
+    #[error("Loan \"{description}\" has an interest rate or number of installments out of range")]
+    InvalidInstallmentPlan { id: Uuid, description: String },
+
+    #[error("ID {id} is used by {count} entries")]
+    DuplicateId { id: Uuid, count: usize },
+
+    #[error("Multiple balance snapshots on {}", .date.format("%d.%m.%Y"))]
+    DuplicateSnapshotDate { date: NaiveDate, ids: Vec<Uuid> },
+}
+
+impl ValidationIssue {
+    /// True if `auto_fix` can resolve this issue without guessing user intent
+    pub fn is_fixable(&self) -> bool {
+        matches!(
+            self,
+            ValidationIssue::DuplicateId { .. } | ValidationIssue::DuplicateSnapshotDate { .. }
+        )
+    }
+
+    /// IDs of the entries involved, used to locate them in the data file
+    pub fn ids(&self) -> Vec<Uuid> {
+        match self {
+            ValidationIssue::InvalidDayOfMonth { id, .. }
+            | ValidationIssue::ZeroAmount { id, .. }
+            | ValidationIssue::InvalidAmountRange { id, .. }
+            | ValidationIssue::EmptyInstallmentPlan { id, .. }
+            | ValidationIssue::InvalidInstallmentPlan { id, .. }
+            | ValidationIssue::DuplicateId { id, .. } => vec![*id],
+            ValidationIssue::DuplicateSnapshotDate { ids, .. } => ids.clone(),
+        }
+    }
+}
+
+impl RecurringTransaction {
+    /// Validates a single recurring transaction, returning the first problem found
+    pub fn validate(&self) -> Result<(), ValidationIssue> {
+        match recurring_issues(self).into_iter().next() {
+            Some(issue) => Err(issue),
+            None => Ok(()),
+        }
+    }
+}
+
+impl OneTimeTransaction {
+    /// Validates a single one-time transaction, returning the first problem found
+    pub fn validate(&self) -> Result<(), ValidationIssue> {
+        match one_time_issues(self).into_iter().next() {
+            Some(issue) => Err(issue),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Collects all problems in the data set
+pub fn validate_data(data: &CashflowData) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    for txn in &data.recurring {
+        issues.extend(recurring_issues(txn));
+    }
+
+    for txn in &data.one_time {
+        issues.extend(one_time_issues(txn));
+    }
+
+    // IDs must be unique across all entries
+    let mut id_counts: HashMap<Uuid, usize> = HashMap::new();
+    for id in all_ids(data) {
+        *id_counts.entry(id).or_default() += 1;
+    }
+    let mut reported = HashSet::new();
+    for id in all_ids(data) {
+        let count = id_counts[&id];
+        if count > 1 && reported.insert(id) {
+            issues.push(ValidationIssue::DuplicateId { id, count });
+        }
+    }
+
+    // Only one balance snapshot per date
+    let mut by_date: Vec<(NaiveDate, Vec<Uuid>)> = Vec::new();
+    for snapshot in &data.balance_snapshots {
+        match by_date.iter_mut().find(|(date, _)| *date == snapshot.date) {
+            Some((_, ids)) => ids.push(snapshot.id),
+            None => by_date.push((snapshot.date, vec![snapshot.id])),
+        }
+    }
+    for (date, ids) in by_date {
+        if ids.len() > 1 {
+            issues.push(ValidationIssue::DuplicateSnapshotDate { date, ids });
+        }
+    }
+
+    issues
+}
+
+/// Fixes the issues that have an unambiguous resolution
+/// Returns a human-readable description of each applied fix
+pub fn auto_fix(data: &mut CashflowData) -> Vec<String> {
+    let mut fixes = Vec::new();
+
+    // Duplicate snapshots: keep the most recently created one for each date
+    let mut dates: Vec<NaiveDate> = data.balance_snapshots.iter().map(|s| s.date).collect();
+    dates.sort();
+    dates.dedup();
+    for date in dates {
+        let keep_index = data
+            .balance_snapshots
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.date == date)
+            .max_by_key(|(_, s)| s.created_at)
+            .map(|(index, _)| index);
+
+        let before = data.balance_snapshots.len();
+        let mut index = 0;
+        data.balance_snapshots.retain(|s| {
+            let keep = s.date != date || Some(index) == keep_index;
+            index += 1;
+            keep
+        });
+        let removed = before - data.balance_snapshots.len();
+        if removed > 0 {
+            fixes.push(format!(
+                "Removed {} older balance snapshot(s) on {}",
+                removed,
+                date.format("%d.%m.%Y")
+            ));
+        }
+    }
+
+    // Duplicate IDs: the first entry keeps its ID, later ones get a fresh one
+    let mut seen = HashSet::new();
+    let mut reassign = |id: &mut Uuid, description: &str| {
+        if !seen.insert(*id) {
+            let new_id = Uuid::new_v4();
+            fixes.push(format!(
+                "Assigned new ID {} to \"{}\" (was {})",
+                new_id, description, id
+            ));
+            *id = new_id;
+            seen.insert(new_id);
+        }
+    };
+    for txn in &mut data.recurring {
+        reassign(&mut txn.id, &txn.description);
+    }
+    for txn in &mut data.one_time {
+        reassign(&mut txn.id, &txn.description);
+    }
+    for snapshot in &mut data.balance_snapshots {
+        let label = format!("balance snapshot {}", snapshot.date.format("%d.%m.%Y"));
+        reassign(&mut snapshot.id, &label);
+    }
+
+    fixes
+}
+
+fn recurring_issues(txn: &RecurringTransaction) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    if !(1..=31).contains(&txn.day_of_month) {
+        issues.push(ValidationIssue::InvalidDayOfMonth {
+            id: txn.id,
+            description: txn.description.clone(),
+            day: txn.day_of_month,
+        });
+    }
+
+    if txn.amount.is_zero() || txn.amount_changes.iter().any(|c| c.amount.is_zero()) {
+        issues.push(ValidationIssue::ZeroAmount {
+            id: txn.id,
+            kind: "Recurring",
+            description: txn.description.clone(),
+        });
+    }
+
+    if txn
+        .amount_range
+        .as_ref()
+        .is_some_and(|range| !range.fits(txn.amount))
+    {
+        issues.push(ValidationIssue::InvalidAmountRange {
+            id: txn.id,
+            description: txn.description.clone(),
+        });
+    }
+
+    if let Some(plan) = &txn.installment_plan {
+        if plan.installments == 0 {
+            issues.push(ValidationIssue::EmptyInstallmentPlan {
+                id: txn.id,
+                description: txn.description.clone(),
+            });
+        } else if !plan.has_valid_terms() {
+            issues.push(ValidationIssue::InvalidInstallmentPlan {
+                id: txn.id,
+                description: txn.description.clone(),
+            });
+        }
+    }
+
+    issues
+}
+
+fn one_time_issues(txn: &OneTimeTransaction) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    if txn.amount.is_zero() {
+        issues.push(ValidationIssue::ZeroAmount {
+            id: txn.id,
+            kind: "One-time",
+            description: txn.description.clone(),
+        });
+    }
+
+    issues
+}
+
+fn all_ids(data: &CashflowData) -> impl Iterator<Item = Uuid> + '_ {
+    data.recurring
+        .iter()
+        .map(|t| t.id)
+        .chain(data.one_time.iter().map(|t| t.id))
+        .chain(data.balance_snapshots.iter().map(|s| s.id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AmountRange, BalanceSnapshot};
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_validate_recurring() {
+        let mut txn = RecurringTransaction::new(
+            "Netflix".to_string(),
+            Decimal::from_str("-478").unwrap(),
+            14,
+        );
+        assert!(txn.validate().is_ok());
+
+        txn.day_of_month = 45;
+        assert!(matches!(
+            txn.validate(),
+            Err(ValidationIssue::InvalidDayOfMonth { day: 45, .. })
+        ));
+
+        txn.day_of_month = 0;
+        assert!(txn.validate().is_err());
+
+        txn.day_of_month = 14;
+        txn.amount = Decimal::ZERO;
+        assert!(matches!(
+            txn.validate(),
+            Err(ValidationIssue::ZeroAmount { .. })
+        ));
+
+        txn.amount = Decimal::from(-2000);
+        txn.amount_range = Some(AmountRange::new(Decimal::from(-1500), Decimal::from(-1800)));
+        assert!(matches!(
+            txn.validate(),
+            Err(ValidationIssue::InvalidAmountRange { .. })
+        ));
+        txn.amount_range = Some(AmountRange::new(Decimal::from(500), Decimal::from(-3000)));
+        assert!(txn.validate().is_err());
+        txn.amount_range = Some(AmountRange::new(Decimal::ZERO, Decimal::from(-3000)));
+        assert!(txn.validate().is_ok());
+
+        txn.amount_range = None;
+        txn.installment_plan = Some(crate::models::InstallmentPlan {
+            principal: None,
+            annual_rate: Decimal::from(300),
+            installments: 360,
+            first_payment: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        });
+        assert!(matches!(
+            txn.validate(),
+            Err(ValidationIssue::InvalidInstallmentPlan { .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_and_fix_duplicates() {
+        let mut data = CashflowData::default();
+        let date = NaiveDate::from_ymd_opt(2025, 10, 13).unwrap();
+
+        let netflix = RecurringTransaction::new(
+            "Netflix".to_string(),
+            Decimal::from_str("-478").unwrap(),
+            14,
+        );
+        let mut copy = netflix.clone();
+        copy.description = "Netflix copy".to_string();
+        data.recurring.push(netflix.clone());
+        data.recurring.push(copy);
+
+        let first = BalanceSnapshot::new(date, Decimal::from_str("1000").unwrap());
+        let mut second = BalanceSnapshot::new(date, Decimal::from_str("2000").unwrap());
+        second.created_at = first.created_at + chrono::Duration::seconds(1);
+        data.balance_snapshots.push(first);
+        data.balance_snapshots.push(second);
+
+        let issues = validate_data(&data);
+        assert_eq!(issues.len(), 2);
+        assert!(issues.iter().all(|i| i.is_fixable()));
+
+        let fixes = auto_fix(&mut data);
+        assert_eq!(fixes.len(), 2);
+        assert!(validate_data(&data).is_empty());
+
+        // The first entry keeps its ID and the newest snapshot wins
+        assert_eq!(data.recurring[0].id, netflix.id);
+        assert_ne!(data.recurring[1].id, netflix.id);
+        assert_eq!(data.balance_snapshots.len(), 1);
+        assert_eq!(
+            data.balance_snapshots[0].balance,
+            Decimal::from_str("2000").unwrap()
+        );
+    }
+}
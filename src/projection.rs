@@ -1,3 +1,23 @@
+mod account;
+mod budget;
 mod cashflow;
+mod check;
+mod goals;
+mod metrics;
+mod report;
+mod risk;
+mod snapshots;
+mod subscriptions;
+mod topup;
 
+pub use account::*;
+pub use budget::*;
 pub use cashflow::*;
+pub use check::*;
+pub use goals::*;
+pub use metrics::*;
+pub use report::*;
+pub use risk::*;
+pub use snapshots::*;
+pub use subscriptions::*;
+pub use topup::*;
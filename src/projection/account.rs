@@ -0,0 +1,159 @@
+use crate::models::{AccountRules, OneTimeTransaction};
+use crate::projection::{ScheduledTransaction, TransactionOrigin, get_transaction_date_in_month};
+use chrono::{Duration, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+/// Applies the account's interest and fee rules to a stream of scheduled transactions
+/// Interest accrues daily on the end-of-day balance and is credited once a month,
+/// so accrued but not yet credited interest is carried between calls
+pub struct AccountLedger<'a> {
+    rules: &'a AccountRules,
+    accrued_interest: Decimal,
+    accrued_overdraft: Decimal,
+}
+
+impl<'a> AccountLedger<'a> {
+    pub fn new(rules: &'a AccountRules) -> Self {
+        Self {
+            rules,
+            accrued_interest: Decimal::ZERO,
+            accrued_overdraft: Decimal::ZERO,
+        }
+    }
+
+    /// Walks the days after `start_date` up to `end_date` and inserts interest and fee rows
+    /// `transactions` must be sorted by date; `opening_balance` is the balance at `start_date`
+    pub(crate) fn apply(
+        &mut self,
+        transactions: Vec<ScheduledTransaction>,
+        opening_balance: Decimal,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Vec<ScheduledTransaction> {
+        if self.rules.is_empty() {
+            return transactions;
+        }
+
+        let days_in_year = Decimal::from(36500); // 365 days × 100 %
+        let mut result = Vec::with_capacity(transactions.len());
+        let mut pending = transactions.into_iter().peekable();
+        let mut balance = opening_balance;
+        let mut day = start_date + Duration::days(1);
+
+        while day <= end_date {
+            while let Some(txn) = pending.next_if(|(date, _, _)| *date <= day) {
+                balance += txn.1.amount;
+                result.push(txn);
+            }
+
+            if !self.rules.monthly_fee.is_zero()
+                && get_transaction_date_in_month(day, self.rules.fee_day) == Some(day)
+            {
+                balance -= self.rules.monthly_fee;
+                result.push(generated(
+                    day,
+                    "Poplatek za vedení účtu",
+                    -self.rules.monthly_fee,
+                ));
+            }
+
+            // Accrue on the end-of-day balance
+            if balance.is_sign_positive() {
+                self.accrued_interest += balance * self.rules.interest_rate / days_in_year;
+            } else {
+                self.accrued_overdraft += balance * self.rules.overdraft_rate / days_in_year;
+            }
+
+            if get_transaction_date_in_month(day, self.rules.interest_day) == Some(day) {
+                let interest = self.accrued_interest.round_dp(2);
+                let overdraft = self.accrued_overdraft.round_dp(2);
+                self.accrued_interest = Decimal::ZERO;
+                self.accrued_overdraft = Decimal::ZERO;
+
+                for (description, amount) in
+                    [("Úroky", interest), ("Úroky z přečerpání", overdraft)]
+                {
+                    if !amount.is_zero() {
+                        balance += amount;
+                        result.push(generated(day, description, amount));
+                    }
+                }
+            }
+
+            day += Duration::days(1);
+        }
+
+        result.extend(pending);
+        result
+    }
+}
+
+fn generated(date: NaiveDate, description: &str, amount: Decimal) -> ScheduledTransaction {
+    let txn = OneTimeTransaction {
+        id: Uuid::nil(),
+        description: description.to_string(),
+        amount,
+        date,
+        created_at: Utc::now(),
+        category: None,
+    };
+    (date, txn, TransactionOrigin::Account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_interest_and_fees() {
+        let rules = AccountRules {
+            interest_rate: Decimal::from(365),
+            overdraft_rate: Decimal::from(730),
+            interest_day: 31,
+            monthly_fee: Decimal::from(99),
+            fee_day: 15,
+        };
+        let mut ledger = AccountLedger::new(&rules);
+
+        // 100 Kč at 365 % p.a. earns 1 Kč a day; from 11.10. on the balance is -100 Kč
+        let withdrawal = generated(date(10, 11), "Výběr", Decimal::from(-200));
+        let result = ledger.apply(
+            vec![(date(10, 11), withdrawal.1, TransactionOrigin::OneTime)],
+            Decimal::from(100),
+            date(9, 30),
+            date(10, 31),
+        );
+        let rows: Vec<_> = result
+            .iter()
+            .map(|(date, txn, _)| (*date, txn.description.as_str(), txn.amount))
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                (date(10, 11), "Výběr", Decimal::from(-200)),
+                (date(10, 15), "Poplatek za vedení účtu", Decimal::from(-99)),
+                (date(10, 31), "Úroky", Decimal::from(10)),
+                // -100 Kč for 4 days, then -199 Kč for 17 days, at 2 % a day
+                (
+                    date(10, 31),
+                    "Úroky z přečerpání",
+                    Decimal::from_str_exact("-75.66").unwrap()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_rules_keep_transactions() {
+        let rules = AccountRules::default();
+        let mut ledger = AccountLedger::new(&rules);
+        let result = ledger.apply(Vec::new(), Decimal::from(1000), date(10, 1), date(12, 31));
+        assert!(result.is_empty());
+    }
+}
@@ -0,0 +1,170 @@
+use crate::models::{Budget, BudgetPeriod, CashflowData};
+use crate::projection::occurrences;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Planned spending of a budget's category in one period
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetStatus {
+    pub budget: Budget,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    /// Expenses minus refunds in the category over the whole period, as a positive number
+    pub planned: Decimal,
+}
+
+impl BudgetStatus {
+    /// Limit left for the period, negative when overspent
+    pub fn remaining(&self) -> Decimal {
+        self.budget.limit - self.planned
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.planned > self.budget.limit
+    }
+}
+
+/// Status of every budget for the period containing `today` and the following
+/// `periods - 1` periods
+/// Past and planned transactions of the period both count, so a budget can be
+/// reported as overspent before the money is actually spent
+pub fn budget_statuses(data: &CashflowData, today: NaiveDate, periods: u32) -> Vec<BudgetStatus> {
+    let mut statuses = Vec::new();
+
+    for budget in &data.budgets {
+        let mut period_start = budget.period.start(today);
+        for _ in 0..periods.max(1) {
+            let period_end = budget.period.end(period_start);
+            statuses.push(BudgetStatus {
+                budget: budget.clone(),
+                period_start,
+                period_end,
+                planned: category_spending(data, budget, period_start, period_end, today),
+            });
+            period_start = budget.period.next(period_start);
+        }
+    }
+
+    statuses
+}
+
+/// Transactions between `from` and `to` (inclusive) that push a category over its
+/// monthly budget, as (transaction ID, date) pairs
+/// Spending earlier in the month counts even if it is before `from`
+pub fn budget_overruns(
+    data: &CashflowData,
+    from: NaiveDate,
+    to: NaiveDate,
+    today: NaiveDate,
+) -> HashSet<(Uuid, NaiveDate)> {
+    let mut overruns = HashSet::new();
+
+    for budget in data
+        .budgets
+        .iter()
+        .filter(|b| b.period == BudgetPeriod::Monthly)
+    {
+        let mut month = BudgetPeriod::Monthly.start(from);
+        while month <= to {
+            let mut spent = Decimal::ZERO;
+            for occurrence in occurrences(data, month, BudgetPeriod::Monthly.end(month), today) {
+                if !budget.matches(occurrence.category.as_deref()) {
+                    continue;
+                }
+                spent -= occurrence.amount;
+
+                let in_window = occurrence.date >= from && occurrence.date <= to;
+                if in_window && occurrence.amount.is_sign_negative() && spent > budget.limit {
+                    overruns.insert((occurrence.id, occurrence.date));
+                }
+            }
+            month = BudgetPeriod::Monthly.next(month);
+        }
+    }
+
+    overruns
+}
+
+fn category_spending(
+    data: &CashflowData,
+    budget: &Budget,
+    from: NaiveDate,
+    to: NaiveDate,
+    today: NaiveDate,
+) -> Decimal {
+    // Subtracting from zero avoids a negative zero when nothing is planned
+    Decimal::ZERO
+        - occurrences(data, from, to, today)
+            .into_iter()
+            .filter(|o| budget.matches(o.category.as_deref()))
+            .map(|o| o.amount)
+            .sum::<Decimal>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{OneTimeTransaction, RecurringTransaction};
+    use chrono::{TimeZone, Utc};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn data_with_food_budget() -> CashflowData {
+        let mut data = CashflowData::default();
+
+        let mut groceries = RecurringTransaction::new("Nákup".to_string(), Decimal::from(-3000), 5);
+        groceries.created_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        groceries.category = Some("Jídlo".to_string());
+        data.recurring.push(groceries);
+
+        let mut dinner = OneTimeTransaction::new(
+            "Večeře".to_string(),
+            Decimal::from(-2500),
+            date(2025, 3, 20),
+        );
+        dinner.category = Some("jídlo".to_string());
+        data.one_time.push(dinner);
+
+        data.budgets.push(Budget {
+            category: "Jídlo".to_string(),
+            period: BudgetPeriod::Monthly,
+            limit: Decimal::from(5000),
+        });
+        data
+    }
+
+    #[test]
+    fn test_budget_statuses() {
+        let data = data_with_food_budget();
+        let statuses = budget_statuses(&data, date(2025, 3, 10), 2);
+
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].period_start, date(2025, 3, 1));
+        assert_eq!(statuses[0].planned, Decimal::from(5500));
+        assert!(statuses[0].is_over());
+        assert_eq!(statuses[0].remaining(), Decimal::from(-500));
+
+        assert_eq!(statuses[1].period_end, date(2025, 4, 30));
+        assert_eq!(statuses[1].planned, Decimal::from(3000));
+        assert!(!statuses[1].is_over());
+    }
+
+    #[test]
+    fn test_budget_overruns() {
+        let data = data_with_food_budget();
+
+        // The groceries on 5.3. are before the window but still count
+        let overruns = budget_overruns(
+            &data,
+            date(2025, 3, 10),
+            date(2025, 4, 30),
+            date(2025, 3, 10),
+        );
+        let dinner_id = data.one_time[0].id;
+        assert_eq!(overruns, HashSet::from([(dinner_id, date(2025, 3, 20))]));
+    }
+}
@@ -1,19 +1,85 @@
 use crate::models::{
-    BalanceSnapshot, CashflowData, OneTimeTransaction, RecurringTransaction, TransactionView,
+    BalanceSnapshot, CashflowData, OccurrenceStatus, OneTimeTransaction, RecurringTransaction,
+    TransactionView,
 };
+use crate::projection::AccountLedger;
 use anyhow::anyhow;
 use chrono::{Datelike, Duration, Local, NaiveDate};
 
+/// Where a scheduled transaction in the projection comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TransactionOrigin {
+    Recurring,
+    OneTime,
+    /// Interest or fee generated from the account rules
+    Account,
+}
+
+/// Transaction scheduled on a date, before running balances are calculated
+pub(crate) type ScheduledTransaction = (NaiveDate, OneTimeTransaction, TransactionOrigin);
+
 pub struct CashflowProjection<'a> {
     pub starting_balance: rust_decimal::Decimal,
     pub start_date: NaiveDate,
     pub balance_snapshot: &'a BalanceSnapshot,
+    /// Days between the balance snapshot and today
+    pub snapshot_age_days: i64,
     pub future_txns: Vec<TransactionView>,
     pub past_txns: Vec<TransactionView>,
 }
 
+impl CashflowProjection<'_> {
+    /// Balance after the last projected transaction
+    pub fn ending_balance(&self) -> rust_decimal::Decimal {
+        self.future_txns
+            .last()
+            .map(|t| t.balance_after)
+            .unwrap_or(self.starting_balance)
+    }
+
+    /// Lowest balance from today until the end of the projection, with its date
+    pub fn min_balance(&self) -> (rust_decimal::Decimal, NaiveDate) {
+        self.future_txns.iter().fold(
+            (self.starting_balance, self.start_date),
+            |(min, date), txn| {
+                if txn.balance_after < min {
+                    (txn.balance_after, txn.date)
+                } else {
+                    (min, date)
+                }
+            },
+        )
+    }
+
+    /// Lowest pessimistic balance, assuming every variable amount hits the worst end of its range
+    pub fn min_worst_balance(&self) -> (rust_decimal::Decimal, NaiveDate) {
+        self.future_txns.iter().fold(
+            (self.starting_balance, self.start_date),
+            |(min, date), txn| {
+                if txn.worst_balance_after < min {
+                    (txn.worst_balance_after, txn.date)
+                } else {
+                    (min, date)
+                }
+            },
+        )
+    }
+
+    /// True if the snapshot is older than `max_age_days`, so the starting balance is
+    /// extrapolated over too many unconfirmed transactions
+    pub fn is_snapshot_stale(&self, max_age_days: i64) -> bool {
+        self.snapshot_age_days > max_age_days
+    }
+
+    /// True if any projected transaction has a variable amount
+    pub fn has_amount_ranges(&self) -> bool {
+        self.future_txns.iter().any(|t| t.worst_amount != t.amount)
+    }
+}
+
 /// Generates cashflow projection for the next N days from today
-/// Returns: (starting_balance, today, projected_transactions)
+/// Transactions the latest snapshot doesn't reflect yet (see `SnapshotTiming`) are applied
+/// up to yesterday to get today's balance, today's transactions belong to the projection
 pub fn project_cashflow<'a>(
     data: &'a CashflowData,
     days: i64,
@@ -22,50 +88,41 @@ pub fn project_cashflow<'a>(
     let snapshot = find_latest_balance_snapshot(data)?;
     let today = Local::now().date_naive();
 
+    // Transactions before this day are already reflected in the snapshot balance
+    let first_open_day = snapshot.first_open_day();
+
     // Step 1: Calculate current balance as of today
-    // Start with snapshot balance and apply all transactions from snapshot date to today (exclusive)
+    // Start with snapshot balance and apply the transactions it doesn't reflect up to yesterday
+    // (today belongs to the projection)
     let mut current_balance = snapshot.balance;
     let mut past_projected: Vec<TransactionView> = Vec::new();
+    let mut ledger = AccountLedger::new(&data.account);
+
+    if first_open_day < today {
+        let balance_calc_end = today - Duration::days(1);
+        let past_transactions = scheduled_between(data, first_open_day, balance_calc_end);
+
+        // Interest and fees depend on the running balance, so they are added after sorting
+        let past_transactions = ledger.apply(
+            past_transactions,
+            snapshot.balance,
+            first_open_day - Duration::days(1),
+            balance_calc_end,
+        );
 
-    if today > snapshot.date {
-        // Calculate balance from snapshot date to today
-        let balance_calc_end = today;
-        let mut past_transactions = Vec::new();
-
-        // Generate recurring transactions from snapshot to today
-        for recurring in &data.recurring {
-            if !recurring.active {
-                continue;
-            }
-            let recurring_txns =
-                generate_recurring_transactions(recurring, snapshot.date, balance_calc_end);
-            past_transactions.extend(recurring_txns);
-        }
-
-        // Add one-time transactions from snapshot to today
-        for one_time in &data.one_time {
-            if one_time.date > snapshot.date && one_time.date < today {
-                past_transactions.push((one_time.date, one_time.clone(), false));
-            }
-        }
-
-        // Sort by date and apply to current balance
-        past_transactions.sort_by(|a, b| {
-            a.0.cmp(&b.0)
-                .then_with(|| a.1.created_at.cmp(&b.1.created_at))
-        });
-
-        for (date, txn, is_recurring) in past_transactions {
+        for (date, txn, origin) in past_transactions {
             current_balance += txn.amount;
 
-            let projected_txn = if is_recurring {
-                if let Some(recurring) = data.recurring.iter().find(|r| r.id == txn.id) {
-                    TransactionView::from_recurring(recurring, date, current_balance)
-                } else {
-                    continue;
+            let projected_txn = match origin {
+                TransactionOrigin::Recurring => {
+                    if let Some(recurring) = data.recurring.iter().find(|r| r.id == txn.id) {
+                        TransactionView::from_recurring(recurring, date, current_balance)
+                    } else {
+                        continue;
+                    }
                 }
-            } else {
-                TransactionView::from_one_time(&txn, current_balance)
+                TransactionOrigin::OneTime => TransactionView::from_one_time(&txn, current_balance),
+                TransactionOrigin::Account => TransactionView::generated(&txn, current_balance),
             };
 
             past_projected.push(projected_txn);
@@ -73,50 +130,55 @@ pub fn project_cashflow<'a>(
     }
 
     // Step 2: Generate projection from today to today + days
+    // (or from the first open day, if the snapshot is dated in the future)
     let end_date = today + Duration::days(days);
-    let mut transactions = Vec::new();
-
-    // Generate recurring transactions from today onwards
-    for recurring in &data.recurring {
-        if !recurring.active {
-            continue;
-        }
-
-        let recurring_txns = generate_recurring_transactions(recurring, today, end_date);
-        transactions.extend(recurring_txns);
-    }
+    let projection_start = today.max(first_open_day);
+    let transactions = scheduled_between(data, projection_start, end_date);
 
-    // Add one-time transactions from today onwards
-    for one_time in &data.one_time {
-        if one_time.date >= today && one_time.date <= end_date {
-            transactions.push((one_time.date, one_time.clone(), false));
-        }
-    }
-
-    // Sort by date, then by creation time (deterministic ordering)
-    transactions.sort_by(|a, b| {
-        a.0.cmp(&b.0)
-            .then_with(|| a.1.created_at.cmp(&b.1.created_at))
-    });
+    let transactions = ledger.apply(
+        transactions,
+        current_balance,
+        projection_start - Duration::days(1),
+        end_date,
+    );
 
     // Step 3: Calculate running balance and create projected transactions
     let starting_balance = current_balance; // Save the starting balance for today
+    let mut worst_balance = current_balance; // Pessimistic balance using the worst end of amount ranges
     let mut projected = Vec::new();
 
-    for (date, txn, is_recurring) in transactions {
+    for (date, txn, origin) in transactions {
         current_balance += txn.amount;
 
-        let projected_txn = if is_recurring {
-            // It's a recurring transaction
-            // Match by id to handle multiple recurring transactions with same description
-            if let Some(recurring) = data.recurring.iter().find(|r| r.id == txn.id) {
-                TransactionView::from_recurring(recurring, date, current_balance)
-            } else {
-                continue;
+        let projected_txn = match origin {
+            TransactionOrigin::Recurring => {
+                // Match by id to handle multiple recurring transactions with same description
+                if let Some(recurring) = data.recurring.iter().find(|r| r.id == txn.id) {
+                    // A one-off amount is exact, so it has no worse case
+                    let worst_amount = match recurring.override_on(date).and_then(|o| o.amount) {
+                        Some(amount) => amount,
+                        None => recurring.worst_amount_on(date),
+                    };
+                    worst_balance += worst_amount;
+                    let mut view =
+                        TransactionView::from_recurring(recurring, date, current_balance);
+                    view.worst_amount = worst_amount;
+                    view.worst_balance_after = worst_balance;
+                    view
+                } else {
+                    continue;
+                }
+            }
+            TransactionOrigin::OneTime | TransactionOrigin::Account => {
+                worst_balance += txn.amount;
+                let mut view = if origin == TransactionOrigin::OneTime {
+                    TransactionView::from_one_time(&txn, current_balance)
+                } else {
+                    TransactionView::generated(&txn, current_balance)
+                };
+                view.worst_balance_after = worst_balance;
+                view
             }
-        } else {
-            // It's a one-time transaction
-            TransactionView::from_one_time(&txn, current_balance)
         };
 
         projected.push(projected_txn);
@@ -126,11 +188,46 @@ pub fn project_cashflow<'a>(
         starting_balance,
         start_date: today,
         balance_snapshot: snapshot,
+        snapshot_age_days: (today - snapshot.date).num_days(),
         future_txns: projected,
         past_txns: past_projected,
     })
 }
 
+/// Active recurring and one-time transactions from `first_day` to `last_day` (both inclusive),
+/// sorted by date, then by creation time (deterministic ordering)
+pub(crate) fn scheduled_between(
+    data: &CashflowData,
+    first_day: NaiveDate,
+    last_day: NaiveDate,
+) -> Vec<ScheduledTransaction> {
+    let mut transactions = Vec::new();
+    if first_day > last_day {
+        return transactions;
+    }
+
+    // The generator's start is exclusive
+    for recurring in data.recurring.iter().filter(|r| r.active) {
+        transactions.extend(generate_recurring_transactions(
+            recurring,
+            first_day - Duration::days(1),
+            last_day,
+        ));
+    }
+
+    for one_time in &data.one_time {
+        if one_time.date >= first_day && one_time.date <= last_day {
+            transactions.push((one_time.date, one_time.clone(), TransactionOrigin::OneTime));
+        }
+    }
+
+    transactions.sort_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.created_at.cmp(&b.1.created_at))
+    });
+    transactions
+}
+
 /// Finds the most recent balance snapshot
 fn find_latest_balance_snapshot(data: &CashflowData) -> anyhow::Result<&BalanceSnapshot> {
     data.balance_snapshots
@@ -139,14 +236,18 @@ fn find_latest_balance_snapshot(data: &CashflowData) -> anyhow::Result<&BalanceS
         .ok_or_else(|| anyhow!("No balance snapshots found. Please set initial balance first."))
 }
 
-/// Generates recurring transaction instances for the projection window
+/// Dates the recurring rule schedules in the window (`start_date` exclusive, `end_date`
+/// inclusive), before occurrence overrides are applied
 /// Handles month boundaries (e.g., day 31 in February becomes last day of month)
-fn generate_recurring_transactions(
+pub(crate) fn scheduled_dates(
     recurring: &RecurringTransaction,
     start_date: NaiveDate,
     end_date: NaiveDate,
-) -> Vec<(NaiveDate, OneTimeTransaction, bool)> {
-    let mut transactions = Vec::new();
+) -> Vec<NaiveDate> {
+    let mut dates = Vec::new();
+    if start_date >= end_date {
+        return dates;
+    }
     let mut current_date = start_date;
 
     // Loop through months until we've passed end_date's month
@@ -155,17 +256,9 @@ fn generate_recurring_transactions(
         if let Some(txn_date) = get_transaction_date_in_month(current_date, recurring.day_of_month)
         {
             // Only include if it's after start_date and within end_date
-            if txn_date > start_date && txn_date <= end_date {
-                // Convert to OneTimeTransaction for uniform processing
-                let one_time = OneTimeTransaction {
-                    id: recurring.id,
-                    description: recurring.description.clone(),
-                    amount: recurring.amount,
-                    date: txn_date,
-                    created_at: recurring.created_at,
-                };
-
-                transactions.push((txn_date, one_time, true));
+            if txn_date > start_date && txn_date <= end_date && recurring.is_scheduled_in(txn_date)
+            {
+                dates.push(txn_date);
             }
         }
 
@@ -181,12 +274,73 @@ fn generate_recurring_transactions(
         }
     }
 
+    dates
+}
+
+/// True if the recurring rule schedules an occurrence on `date`
+pub(crate) fn is_scheduled_on(recurring: &RecurringTransaction, date: NaiveDate) -> bool {
+    scheduled_dates(recurring, date - Duration::days(1), date) == [date]
+}
+
+/// Generates recurring transaction instances for the projection window
+/// (`start_date` exclusive, `end_date` inclusive)
+/// Skipped occurrences are left out, moved ones appear on their new date (also when moved
+/// into the window from outside) and one-off amounts replace the usual amount
+pub(crate) fn generate_recurring_transactions(
+    recurring: &RecurringTransaction,
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> Vec<ScheduledTransaction> {
+    let in_window = |date: NaiveDate| date > start_date && date <= end_date;
+
+    // Occurrences moved in from outside the window
+    let moved_in = recurring
+        .overrides
+        .iter()
+        .filter(|o| {
+            o.moved_to.is_some_and(in_window)
+                && !in_window(o.date)
+                && is_scheduled_on(recurring, o.date)
+        })
+        .map(|o| o.date);
+
+    let mut transactions: Vec<ScheduledTransaction> =
+        scheduled_dates(recurring, start_date, end_date)
+            .into_iter()
+            .chain(moved_in)
+            .filter_map(|scheduled| {
+                let occurrence = recurring.occurrence_override(scheduled);
+                if occurrence.is_some_and(|o| o.status == OccurrenceStatus::Skipped) {
+                    return None;
+                }
+                let date = occurrence.map_or(scheduled, |o| o.actual_date());
+                if !in_window(date) {
+                    return None;
+                }
+
+                // Convert to OneTimeTransaction for uniform processing
+                let one_time = OneTimeTransaction {
+                    id: recurring.id,
+                    description: recurring.description.clone(),
+                    amount: recurring.occurrence_amount(scheduled),
+                    date,
+                    created_at: recurring.created_at,
+                    category: recurring.category.clone(),
+                };
+                Some((date, one_time, TransactionOrigin::Recurring))
+            })
+            .collect();
+
+    transactions.sort_by_key(|(date, _, _)| *date);
     transactions
 }
 
 /// Gets the actual date for a recurring transaction in a given month
 /// Handles edge cases like day 31 in months with fewer days
-fn get_transaction_date_in_month(base_date: NaiveDate, day_of_month: u8) -> Option<NaiveDate> {
+pub(crate) fn get_transaction_date_in_month(
+    base_date: NaiveDate,
+    day_of_month: u8,
+) -> Option<NaiveDate> {
     let year = base_date.year();
     let month = base_date.month();
 
@@ -233,7 +387,7 @@ fn next_month(date: NaiveDate) -> NaiveDate {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::RecurringTransaction;
+    use crate::models::{AmountRange, RecurringTransaction, SnapshotTiming};
     use rust_decimal::Decimal;
     use std::str::FromStr;
 
@@ -363,4 +517,252 @@ mod tests {
             "Should have Služby transaction on day 20 with amount -2940"
         );
     }
+
+    #[test]
+    fn test_recurring_amount_history() {
+        let mut netflix = RecurringTransaction::new(
+            "Netflix".to_string(),
+            Decimal::from_str("-199").unwrap(),
+            14,
+        );
+        netflix.schedule_amount(
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            Decimal::from_str("-229").unwrap(),
+        );
+
+        let txns = generate_recurring_transactions(
+            &netflix,
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 4, 30).unwrap(),
+        );
+        let amounts: Vec<_> = txns.iter().map(|(_, t, _)| t.amount).collect();
+
+        // February keeps the old price, March on uses the new one
+        assert_eq!(
+            amounts,
+            vec![
+                Decimal::from_str("-199").unwrap(),
+                Decimal::from_str("-229").unwrap(),
+                Decimal::from_str("-229").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_worst_case_balance() {
+        let mut data = CashflowData::default();
+        let today = Local::now().date_naive();
+        data.balance_snapshots
+            .push(BalanceSnapshot::new(today, Decimal::from(10000)));
+
+        // Electricity expected at -2 000, but can be up to -3 500
+        let mut electricity =
+            RecurringTransaction::new("Elektřina".to_string(), Decimal::from(-2000), 1);
+        electricity.amount_range =
+            Some(AmountRange::new(Decimal::from(-1500), Decimal::from(-3500)));
+        data.recurring.push(electricity);
+
+        let projection = project_cashflow(&data, 31).unwrap();
+        let txn = &projection.future_txns[0];
+
+        assert!(projection.has_amount_ranges());
+        assert_eq!(txn.balance_after, Decimal::from(8000));
+        assert_eq!(txn.worst_balance_after, Decimal::from(6500));
+        assert_eq!(projection.min_worst_balance().0, Decimal::from(6500));
+    }
+
+    #[test]
+    fn test_amount_range_follows_amount_changes() {
+        let date = |month| NaiveDate::from_ymd_opt(2025, month, 1).unwrap();
+        let mut electricity =
+            RecurringTransaction::new("Elektřina".to_string(), Decimal::from(-2000), 1);
+        electricity.amount_range =
+            Some(AmountRange::new(Decimal::from(-1500), Decimal::from(-3500)));
+        electricity.schedule_amount(date(7), Decimal::from(-2200));
+
+        // A 10 % price increase moves the whole range by 10 %
+        assert_eq!(electricity.worst_amount_on(date(6)), Decimal::from(-3500));
+        assert_eq!(electricity.worst_amount_on(date(7)), Decimal::from(-3850));
+        assert_eq!(
+            electricity.range_on(date(7)),
+            Some(AmountRange::new(Decimal::from(-1650), Decimal::from(-3850)))
+        );
+
+        // A range given for the new amount is stored for the base amount
+        electricity.set_range_on(
+            date(7),
+            AmountRange::new(Decimal::from(-1100), Decimal::from(-4400)),
+        );
+        assert_eq!(
+            electricity.amount_range,
+            Some(AmountRange::new(Decimal::from(-1000), Decimal::from(-4000)))
+        );
+
+        electricity.set_amount(Decimal::from(-1000));
+        assert_eq!(
+            electricity.amount_range,
+            Some(AmountRange::new(Decimal::from(-500), Decimal::from(-2000)))
+        );
+    }
+
+    /// Snapshot of 1 000 on `date` with a recurring (-100) and a one-time (-10) payment
+    /// on the same day
+    fn snapshot_day_data(date: NaiveDate, timing: SnapshotTiming) -> CashflowData {
+        let mut data = CashflowData::default();
+        let mut snapshot = BalanceSnapshot::new(date, Decimal::from(1000));
+        snapshot.timing = timing;
+        data.balance_snapshots.push(snapshot);
+        data.recurring.push(RecurringTransaction::new(
+            "Nájem".to_string(),
+            Decimal::from(-100),
+            date.day() as u8,
+        ));
+        data.one_time.push(OneTimeTransaction::new(
+            "Oprava".to_string(),
+            Decimal::from(-10),
+            date,
+        ));
+        data
+    }
+
+    #[test]
+    fn test_past_snapshot_timing() {
+        let today = Local::now().date_naive();
+        let date = today - Duration::days(3);
+
+        // Taken after the day's payments: both are already in the balance
+        let data = snapshot_day_data(date, SnapshotTiming::After);
+        let projection = project_cashflow(&data, 10).unwrap();
+        assert!(projection.past_txns.is_empty());
+        assert_eq!(projection.starting_balance, Decimal::from(1000));
+
+        // Taken before them: both still apply, recurring and one-time alike
+        let data = snapshot_day_data(date, SnapshotTiming::Before);
+        let projection = project_cashflow(&data, 10).unwrap();
+        assert_eq!(projection.past_txns.len(), 2);
+        assert!(projection.past_txns.iter().all(|t| t.date == date));
+        assert_eq!(projection.starting_balance, Decimal::from(890));
+        assert!(projection.future_txns.is_empty());
+    }
+
+    #[test]
+    fn test_today_snapshot_timing() {
+        let today = Local::now().date_naive();
+
+        let data = snapshot_day_data(today, SnapshotTiming::After);
+        let projection = project_cashflow(&data, 10).unwrap();
+        assert!(projection.future_txns.is_empty());
+        assert_eq!(projection.ending_balance(), Decimal::from(1000));
+
+        let data = snapshot_day_data(today, SnapshotTiming::Before);
+        let projection = project_cashflow(&data, 10).unwrap();
+        assert!(projection.past_txns.is_empty());
+        assert_eq!(projection.starting_balance, Decimal::from(1000));
+        assert_eq!(projection.future_txns.len(), 2);
+        assert!(projection.future_txns.iter().all(|t| t.date == today));
+        assert_eq!(projection.ending_balance(), Decimal::from(890));
+    }
+
+    #[test]
+    fn test_payments_due_today_after_past_snapshot() {
+        let today = Local::now().date_naive();
+        let mut data = snapshot_day_data(today - Duration::days(3), SnapshotTiming::After);
+        data.recurring[0].day_of_month = today.day() as u8;
+        data.one_time[0].date = today;
+
+        // Today is not part of the current balance, its payments belong to the projection
+        let projection = project_cashflow(&data, 10).unwrap();
+        assert!(projection.past_txns.is_empty());
+        assert_eq!(projection.starting_balance, Decimal::from(1000));
+        assert_eq!(projection.future_txns.len(), 2);
+        assert!(projection.future_txns.iter().all(|t| t.date == today));
+    }
+
+    #[test]
+    fn test_future_snapshot_timing() {
+        let today = Local::now().date_naive();
+        let date = today + Duration::days(2);
+
+        // Payments between today and the snapshot are reflected in it
+        let mut data = snapshot_day_data(date, SnapshotTiming::After);
+        data.one_time.push(OneTimeTransaction::new(
+            "Zítra".to_string(),
+            Decimal::from(-1),
+            today + Duration::days(1),
+        ));
+        let projection = project_cashflow(&data, 10).unwrap();
+        assert!(projection.future_txns.is_empty());
+
+        let data = snapshot_day_data(date, SnapshotTiming::Before);
+        let projection = project_cashflow(&data, 10).unwrap();
+        assert_eq!(projection.future_txns.len(), 2);
+        assert_eq!(projection.ending_balance(), Decimal::from(890));
+    }
+
+    #[test]
+    fn test_stale_snapshot() {
+        let mut data = CashflowData::default();
+        let today = Local::now().date_naive();
+        data.balance_snapshots.push(BalanceSnapshot::new(
+            today - Duration::days(40),
+            Decimal::from(10000),
+        ));
+        data.one_time.push(OneTimeTransaction::new(
+            "Oprava auta".to_string(),
+            Decimal::from(-3000),
+            today - Duration::days(10),
+        ));
+
+        let projection = project_cashflow(&data, 30).unwrap();
+
+        assert_eq!(projection.snapshot_age_days, 40);
+        assert!(projection.is_snapshot_stale(14));
+        assert!(!projection.is_snapshot_stale(40));
+        assert_eq!(projection.past_txns.len(), 1);
+        assert_eq!(projection.starting_balance, Decimal::from(7000));
+    }
+
+    #[test]
+    fn test_occurrence_overrides() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+        let mut rent = RecurringTransaction::new("Nájem".to_string(), Decimal::from(-5000), 20);
+        rent.update_occurrence(date(2, 20), |o| o.status = OccurrenceStatus::Skipped);
+        rent.update_occurrence(date(3, 20), |o| o.amount = Some(Decimal::from(-10000)));
+        // Moved into the window from after its end, and out of it from inside
+        rent.update_occurrence(date(5, 20), |o| o.moved_to = Some(date(4, 2)));
+        rent.update_occurrence(date(4, 20), |o| o.moved_to = Some(date(5, 5)));
+
+        let txns = generate_recurring_transactions(&rent, date(1, 31), date(4, 30));
+        let rows: Vec<_> = txns.iter().map(|(d, t, _)| (*d, t.amount)).collect();
+        assert_eq!(
+            rows,
+            vec![
+                (date(3, 20), Decimal::from(-10000)),
+                (date(4, 2), Decimal::from(-5000)),
+            ]
+        );
+
+        // Undoing the only change drops the override
+        rent.update_occurrence(date(2, 20), |o| o.status = OccurrenceStatus::Pending);
+        assert_eq!(rent.overrides.len(), 3);
+    }
+
+    #[test]
+    fn test_confirmed_occurrence_view() {
+        let today = Local::now().date_naive();
+        let date = today - Duration::days(3);
+        let mut data = snapshot_day_data(date - Duration::days(1), SnapshotTiming::After);
+        data.one_time.clear();
+        data.recurring[0].day_of_month = date.day() as u8;
+        data.recurring[0].update_occurrence(date, |o| {
+            o.status = OccurrenceStatus::Confirmed;
+            o.amount = Some(Decimal::from(-150));
+        });
+
+        let projection = project_cashflow(&data, 10).unwrap();
+        assert_eq!(projection.past_txns.len(), 1);
+        assert!(projection.past_txns[0].confirmed);
+        assert_eq!(projection.past_txns[0].worst_amount, Decimal::from(-150));
+        assert_eq!(projection.starting_balance, Decimal::from(850));
+    }
 }
@@ -0,0 +1,248 @@
+use crate::projection::CashflowProjection;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::fmt;
+
+/// Rules evaluated by `cashflow check`
+#[derive(Debug, Clone)]
+pub struct CheckRules {
+    /// Balance that must not be undercut within the projection
+    pub threshold: Decimal,
+    /// Single debits of at least this amount (positive number) are reported
+    pub large_debit: Option<Decimal>,
+    /// Snapshots older than this many days are reported
+    pub stale_after_days: Option<i64>,
+}
+
+/// Process exit code when the check itself fails (UNKNOWN in monitoring terms), e.g. the
+/// data file cannot be read or there is no balance snapshot
+pub const CHECK_ERROR_EXIT_CODE: i32 = 3;
+
+/// Overall result of a check, ordered from best to worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warning,
+    Critical,
+}
+
+impl CheckStatus {
+    /// Process exit code: 0 when all rules pass, 1 for warnings, 2 for a negative balance
+    pub fn exit_code(self) -> i32 {
+        match self {
+            CheckStatus::Ok => 0,
+            CheckStatus::Warning => 1,
+            CheckStatus::Critical => 2,
+        }
+    }
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            CheckStatus::Ok => "OK",
+            CheckStatus::Warning => "WARNING",
+            CheckStatus::Critical => "CRITICAL",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// Failed rule with the figures needed to describe it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum Alert {
+    /// Balance goes below zero, first on `date`
+    NegativeBalance { date: NaiveDate, balance: Decimal },
+    /// Balance drops below the threshold, first on `date`
+    BelowThreshold {
+        date: NaiveDate,
+        balance: Decimal,
+        threshold: Decimal,
+    },
+    /// Single upcoming debit of at least the configured amount
+    LargeDebit {
+        date: NaiveDate,
+        description: String,
+        amount: Decimal,
+    },
+    /// Latest balance snapshot is older than allowed
+    StaleSnapshot {
+        date: NaiveDate,
+        age_days: i64,
+        max_age_days: i64,
+    },
+}
+
+impl Alert {
+    pub fn status(&self) -> CheckStatus {
+        match self {
+            Alert::NegativeBalance { .. } => CheckStatus::Critical,
+            _ => CheckStatus::Warning,
+        }
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            Alert::NegativeBalance { date, .. }
+            | Alert::BelowThreshold { date, .. }
+            | Alert::LargeDebit { date, .. }
+            | Alert::StaleSnapshot { date, .. } => *date,
+        }
+    }
+}
+
+/// Evaluates the rules against a projection, alerts are sorted by severity, then by date
+pub fn evaluate_rules(projection: &CashflowProjection, rules: &CheckRules) -> Vec<Alert> {
+    let today = projection.start_date;
+    let mut alerts = Vec::new();
+
+    // The starting balance counts as the balance of today
+    let balances = std::iter::once((today, projection.starting_balance)).chain(
+        projection
+            .future_txns
+            .iter()
+            .map(|t| (t.date, t.balance_after)),
+    );
+    let mut negative = None;
+    let mut below_threshold = None;
+    for (date, balance) in balances {
+        if negative.is_none() && balance < Decimal::ZERO {
+            negative = Some(Alert::NegativeBalance { date, balance });
+        }
+        if below_threshold.is_none() && balance < rules.threshold {
+            below_threshold = Some(Alert::BelowThreshold {
+                date,
+                balance,
+                threshold: rules.threshold,
+            });
+        }
+    }
+    alerts.extend(negative);
+    alerts.extend(below_threshold);
+
+    if let Some(limit) = rules.large_debit {
+        alerts.extend(
+            projection
+                .future_txns
+                .iter()
+                .filter(|t| t.amount <= -limit)
+                .map(|t| Alert::LargeDebit {
+                    date: t.date,
+                    description: t.description.clone(),
+                    amount: t.amount,
+                }),
+        );
+    }
+
+    if let Some(max_age_days) = rules.stale_after_days
+        && projection.is_snapshot_stale(max_age_days)
+    {
+        alerts.push(Alert::StaleSnapshot {
+            date: projection.balance_snapshot.date,
+            age_days: projection.snapshot_age_days,
+            max_age_days,
+        });
+    }
+
+    alerts.sort_by(|a, b| b.status().cmp(&a.status()).then(a.date().cmp(&b.date())));
+    alerts
+}
+
+/// Worst status among the alerts, OK if there are none
+pub fn overall_status(alerts: &[Alert]) -> CheckStatus {
+    alerts
+        .iter()
+        .map(Alert::status)
+        .max()
+        .unwrap_or(CheckStatus::Ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BalanceSnapshot, CashflowData, RecurringTransaction};
+    use crate::projection::project_cashflow;
+    use chrono::{Datelike, Duration, Local};
+
+    fn rules(threshold: i64) -> CheckRules {
+        CheckRules {
+            threshold: Decimal::from(threshold),
+            large_debit: Some(Decimal::from(8000)),
+            stale_after_days: Some(14),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_rules() {
+        let today = Local::now().date_naive();
+        let in_five_days = today + Duration::days(5);
+
+        let mut data = CashflowData::default();
+        data.balance_snapshots
+            .push(BalanceSnapshot::new(today, Decimal::from(6000)));
+        data.recurring.push(RecurringTransaction::new(
+            "Nájem".to_string(),
+            Decimal::from(-9000),
+            in_five_days.day() as u8,
+        ));
+        let projection = project_cashflow(&data, 10).unwrap();
+
+        let alerts = evaluate_rules(&projection, &rules(10000));
+        assert_eq!(
+            alerts,
+            vec![
+                Alert::NegativeBalance {
+                    date: in_five_days,
+                    balance: Decimal::from(-3000),
+                },
+                Alert::BelowThreshold {
+                    date: today,
+                    balance: Decimal::from(6000),
+                    threshold: Decimal::from(10000),
+                },
+                Alert::LargeDebit {
+                    date: in_five_days,
+                    description: "Nájem".to_string(),
+                    amount: Decimal::from(-9000),
+                },
+            ]
+        );
+        assert_eq!(overall_status(&alerts), CheckStatus::Critical);
+        assert_eq!(overall_status(&alerts).exit_code(), 2);
+    }
+
+    #[test]
+    fn test_stale_snapshot_and_ok() {
+        let today = Local::now().date_naive();
+
+        let mut data = CashflowData::default();
+        data.balance_snapshots.push(BalanceSnapshot::new(
+            today - Duration::days(20),
+            Decimal::from(50000),
+        ));
+        let projection = project_cashflow(&data, 10).unwrap();
+
+        let alerts = evaluate_rules(&projection, &rules(10000));
+        assert_eq!(
+            alerts,
+            vec![Alert::StaleSnapshot {
+                date: today - Duration::days(20),
+                age_days: 20,
+                max_age_days: 14,
+            }]
+        );
+        assert_eq!(overall_status(&alerts).exit_code(), 1);
+
+        let relaxed = CheckRules {
+            stale_after_days: Some(30),
+            ..rules(10000)
+        };
+        assert_eq!(
+            overall_status(&evaluate_rules(&projection, &relaxed)),
+            CheckStatus::Ok
+        );
+    }
+}
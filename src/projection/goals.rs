@@ -0,0 +1,148 @@
+use crate::models::{CashflowData, Goal};
+use crate::projection::{get_transaction_date_in_month, occurrences};
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+
+/// Day of month contributions are assumed on while a goal has no recurring transfer
+pub const DEFAULT_CONTRIBUTION_DAY: u8 = 1;
+
+/// How far a goal is and what it takes to reach it in time
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgress {
+    pub goal: Goal,
+    /// Paid by the goal's recurring transfer so far
+    pub contributed: Decimal,
+    /// Still missing after earmarked money and past contributions
+    pub missing: Decimal,
+    /// Contributions left until the target date
+    pub contributions_left: u32,
+    /// Monthly contribution needed to reach the target, rounded up
+    pub required_monthly: Decimal,
+}
+
+impl GoalProgress {
+    /// Earmarked money plus past contributions
+    pub fn saved(&self) -> Decimal {
+        self.goal.earmarked + self.contributed
+    }
+}
+
+/// Computes the progress of a goal, with contributions on `day` of each month
+/// from tomorrow until the target date
+pub fn goal_progress(data: &CashflowData, goal: &Goal, today: NaiveDate, day: u8) -> GoalProgress {
+    let contributed = match goal.contribution_id {
+        Some(id) => {
+            Decimal::ZERO
+                - occurrences(data, goal.created_at.date_naive(), today, today)
+                    .into_iter()
+                    .filter(|o| o.id == id)
+                    .map(|o| o.amount)
+                    .sum::<Decimal>()
+        }
+        None => Decimal::ZERO,
+    };
+
+    let missing = (goal.target - goal.earmarked - contributed).max(Decimal::ZERO);
+    let contributions_left = contribution_count(day, today, goal.target_date);
+    let required_monthly = match contributions_left {
+        _ if missing.is_zero() => Decimal::ZERO,
+        0 => missing,
+        n => (missing / Decimal::from(n)).ceil(),
+    };
+
+    GoalProgress {
+        goal: goal.clone(),
+        contributed,
+        missing,
+        contributions_left,
+        required_monthly,
+    }
+}
+
+/// Total money reserved for goals on the given date
+pub fn earmarked_on(goals: &[Goal], date: NaiveDate) -> Decimal {
+    goals.iter().map(|g| g.earmarked_on(date)).sum()
+}
+
+/// Number of monthly dates on `day` after `today` up to `target_date` (inclusive)
+fn contribution_count(day: u8, today: NaiveDate, target_date: NaiveDate) -> u32 {
+    let mut count = 0;
+    let mut month = today.with_day(1).unwrap_or(today);
+    while month <= target_date {
+        if let Some(date) = get_transaction_date_in_month(month, day)
+            && date > today
+            && date <= target_date
+        {
+            count += 1;
+        }
+        month = month + Months::new(1);
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RecurringTransaction;
+    use chrono::{TimeZone, Utc};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_goal_progress() {
+        let mut data = CashflowData::default();
+        let mut goal = Goal::new(
+            "Dovolená".to_string(),
+            Decimal::from(60000),
+            date(2025, 7, 1),
+        );
+        goal.created_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        goal.earmarked = Decimal::from(10000);
+
+        // Before funding: 50 000 in contributions on 1.2. … 1.7.
+        let progress = goal_progress(&data, &goal, date(2025, 1, 10), 1);
+        assert_eq!(progress.contributions_left, 6);
+        assert_eq!(progress.required_monthly, Decimal::from(8334));
+
+        // Two transfers of 5 000 already went out
+        let mut transfer =
+            RecurringTransaction::new("Spoření".to_string(), Decimal::from(-5000), 15);
+        transfer.created_at = goal.created_at;
+        goal.contribution_id = Some(transfer.id);
+        data.recurring.push(transfer);
+
+        let progress = goal_progress(&data, &goal, date(2025, 3, 1), 15);
+        assert_eq!(progress.contributed, Decimal::from(10000));
+        assert_eq!(progress.saved(), Decimal::from(20000));
+        assert_eq!(progress.missing, Decimal::from(40000));
+        // 15.3., 15.4., 15.5., 15.6.
+        assert_eq!(progress.contributions_left, 4);
+        assert_eq!(progress.required_monthly, Decimal::from(10000));
+    }
+
+    #[test]
+    fn test_earmarked_on() {
+        let mut holiday = Goal::new(
+            "Dovolená".to_string(),
+            Decimal::from(60000),
+            date(2025, 7, 1),
+        );
+        holiday.earmarked = Decimal::from(10000);
+        let mut insurance = Goal::new(
+            "Pojištění".to_string(),
+            Decimal::from(12000),
+            date(2025, 3, 31),
+        );
+        insurance.earmarked = Decimal::from(4000);
+        let goals = vec![holiday, insurance];
+
+        assert_eq!(
+            earmarked_on(&goals, date(2025, 3, 31)),
+            Decimal::from(14000)
+        );
+        assert_eq!(earmarked_on(&goals, date(2025, 4, 1)), Decimal::from(10000));
+        assert_eq!(earmarked_on(&goals, date(2025, 7, 2)), Decimal::ZERO);
+    }
+}
@@ -0,0 +1,167 @@
+use crate::models::CashflowData;
+use crate::projection::CashflowProjection;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use std::fmt::Write;
+
+/// Key figures of a projection, exported for monitoring
+#[derive(Debug, Clone, PartialEq)]
+pub struct CashflowMetrics {
+    pub balance: Decimal,
+    pub projection_days: i64,
+    pub min_balance: Decimal,
+    pub min_balance_date: NaiveDate,
+    pub warning_threshold: Decimal,
+    /// Days from today until the balance first drops below the threshold,
+    /// None if it stays above it for the whole projection
+    pub days_until_below_threshold: Option<i64>,
+    /// Sum of active recurring expenses at today's amounts, as a positive number
+    pub monthly_recurring_burn: Decimal,
+}
+
+impl CashflowMetrics {
+    pub fn new(
+        data: &CashflowData,
+        projection: &CashflowProjection,
+        projection_days: i64,
+        warning_threshold: Decimal,
+    ) -> Self {
+        let today = projection.start_date;
+        let (min_balance, min_balance_date) = projection.min_balance();
+
+        let days_until_below_threshold = if projection.starting_balance < warning_threshold {
+            Some(0)
+        } else {
+            projection
+                .future_txns
+                .iter()
+                .find(|t| t.balance_after < warning_threshold)
+                .map(|t| (t.date - today).num_days())
+        };
+
+        let monthly_recurring_burn = Decimal::ZERO
+            - data
+                .recurring
+                .iter()
+                .filter(|r| r.active && r.is_scheduled_in(today))
+                .map(|r| r.amount_on(today))
+                .filter(|amount| amount.is_sign_negative())
+                .sum::<Decimal>();
+
+        Self {
+            balance: projection.starting_balance,
+            projection_days,
+            min_balance,
+            min_balance_date,
+            warning_threshold,
+            days_until_below_threshold,
+            monthly_recurring_burn,
+        }
+    }
+
+    /// Renders the metrics in the Prometheus text exposition format
+    pub fn to_prometheus(&self) -> String {
+        let min_balance_timestamp = self
+            .min_balance_date
+            .and_hms_opt(0, 0, 0)
+            .map_or(0, |t| t.and_utc().timestamp());
+        // +Inf: the balance stays above the threshold for the whole projection
+        let days_until_below = self
+            .days_until_below_threshold
+            .map_or_else(|| "+Inf".to_string(), |days| days.to_string());
+
+        let metrics = [
+            (
+                "cashflow_balance",
+                "Current account balance",
+                self.balance.to_string(),
+            ),
+            (
+                "cashflow_projection_days",
+                "Number of days the projection looks ahead",
+                self.projection_days.to_string(),
+            ),
+            (
+                "cashflow_projected_min_balance",
+                "Lowest projected balance within the projection",
+                self.min_balance.to_string(),
+            ),
+            (
+                "cashflow_projected_min_balance_timestamp_seconds",
+                "Date of the lowest projected balance as a Unix timestamp",
+                min_balance_timestamp.to_string(),
+            ),
+            (
+                "cashflow_warning_threshold",
+                "Balance below which the plan warns",
+                self.warning_threshold.to_string(),
+            ),
+            (
+                "cashflow_days_until_below_threshold",
+                "Days until the balance drops below the warning threshold",
+                days_until_below,
+            ),
+            (
+                "cashflow_monthly_recurring_burn",
+                "Monthly sum of active recurring expenses",
+                self.monthly_recurring_burn.to_string(),
+            ),
+        ];
+
+        let mut output = String::new();
+        for (name, help, value) in metrics {
+            // Writing to a String cannot fail
+            let _ = writeln!(output, "# HELP {} {}", name, help);
+            let _ = writeln!(output, "# TYPE {} gauge", name);
+            let _ = writeln!(output, "{} {}", name, value);
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BalanceSnapshot, RecurringTransaction};
+    use crate::projection::project_cashflow;
+    use chrono::{Datelike, Duration, Local};
+
+    #[test]
+    fn test_metrics() {
+        let today = Local::now().date_naive();
+        let in_five_days = today + Duration::days(5);
+
+        let mut data = CashflowData::default();
+        data.balance_snapshots
+            .push(BalanceSnapshot::new(today, Decimal::from(12000)));
+        data.recurring.push(RecurringTransaction::new(
+            "Nájem".to_string(),
+            Decimal::from(-5000),
+            in_five_days.day() as u8,
+        ));
+        data.recurring.push(RecurringTransaction::new(
+            "Výplata".to_string(),
+            Decimal::from(40000),
+            (in_five_days + Duration::days(1)).day() as u8,
+        ));
+
+        let projection = project_cashflow(&data, 10).unwrap();
+        let metrics = CashflowMetrics::new(&data, &projection, 10, Decimal::from(10000));
+
+        assert_eq!(metrics.balance, Decimal::from(12000));
+        assert_eq!(metrics.min_balance, Decimal::from(7000));
+        assert_eq!(metrics.min_balance_date, in_five_days);
+        assert_eq!(metrics.days_until_below_threshold, Some(5));
+        assert_eq!(metrics.monthly_recurring_burn, Decimal::from(5000));
+
+        let text = metrics.to_prometheus();
+        assert!(text.contains("# TYPE cashflow_balance gauge\ncashflow_balance 12000\n"));
+        assert!(text.contains("cashflow_days_until_below_threshold 5\n"));
+
+        let safe = CashflowMetrics::new(&data, &projection, 10, Decimal::from(1000));
+        assert!(
+            safe.to_prometheus()
+                .contains("cashflow_days_until_below_threshold +Inf\n")
+        );
+    }
+}
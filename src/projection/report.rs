@@ -0,0 +1,312 @@
+use crate::models::CashflowData;
+use crate::projection::generate_recurring_transactions;
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Category label for transactions without a category
+pub const UNCATEGORIZED: &str = "Uncategorized";
+
+/// Income and expenses of a single month
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MonthSummary {
+    pub year: i32,
+    pub month: u32,
+    pub income: Decimal,
+    /// Sum of expenses as a negative number
+    pub expenses: Decimal,
+    pub net: Decimal,
+    /// True if the month is not over yet and contains projected transactions
+    pub projected: bool,
+    /// Net of the same month a year earlier, None without history for it
+    pub net_year_ago: Option<Decimal>,
+}
+
+/// Totals of a single category over the whole report period
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CategorySummary {
+    pub category: String,
+    pub income: Decimal,
+    pub expenses: Decimal,
+    pub net: Decimal,
+    pub monthly_average: Decimal,
+}
+
+/// Monthly and per-category summary of historical and projected transactions
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub months: Vec<MonthSummary>,
+    pub categories: Vec<CategorySummary>,
+    pub average_income: Decimal,
+    pub average_expenses: Decimal,
+    pub average_net: Decimal,
+}
+
+/// Builds a report for the months from `first_month` to `last_month` (inclusive)
+/// History comes from past one-time transactions (pass data merged with the archive) and
+/// past occurrences of recurring transactions since they were created, including ones
+/// disabled since; the rest of the period is projected from active transactions
+pub fn build_report(
+    data: &CashflowData,
+    first_month: NaiveDate,
+    last_month: NaiveDate,
+    today: NaiveDate,
+) -> Report {
+    let from = first_day(first_month);
+    let to = last_day(last_month);
+    // One more year of history for the year-over-year comparison
+    let history_from = from - Months::new(12);
+
+    let mut by_month: BTreeMap<(i32, u32), (Decimal, Decimal)> = BTreeMap::new();
+    let mut by_category: BTreeMap<String, (Decimal, Decimal)> = BTreeMap::new();
+
+    for occurrence in occurrences(data, history_from, to, today) {
+        let date = occurrence.date;
+        let (income, expenses) = by_month.entry((date.year(), date.month())).or_default();
+        add_amount(income, expenses, occurrence.amount);
+
+        if date >= from {
+            let label = occurrence
+                .category
+                .unwrap_or_else(|| UNCATEGORIZED.to_string());
+            let (income, expenses) = by_category.entry(label).or_default();
+            add_amount(income, expenses, occurrence.amount);
+        }
+    }
+
+    let month_count = months_between(from, to);
+    let history_start = first_day(earliest_history(data));
+    let mut months = Vec::new();
+    let mut month = from;
+    while month <= to {
+        let key = (month.year(), month.month());
+        let (income, expenses) = by_month.get(&key).copied().unwrap_or_default();
+        // Months before any known transaction are unknown rather than zero
+        let net_year_ago = (month - Months::new(12) >= history_start).then(|| {
+            by_month
+                .get(&(month.year() - 1, month.month()))
+                .map_or(Decimal::ZERO, |(income, expenses)| income + expenses)
+        });
+
+        months.push(MonthSummary {
+            year: key.0,
+            month: key.1,
+            income,
+            expenses,
+            net: income + expenses,
+            projected: last_day(month) >= today,
+            net_year_ago,
+        });
+        month = month + Months::new(1);
+    }
+
+    let average = |total: Decimal| (total / Decimal::from(month_count)).round_dp(2);
+    let categories = by_category
+        .into_iter()
+        .map(|(category, (income, expenses))| CategorySummary {
+            category,
+            income,
+            expenses,
+            net: income + expenses,
+            monthly_average: average(income + expenses),
+        })
+        .collect();
+
+    let total_income: Decimal = months.iter().map(|m| m.income).sum();
+    let total_expenses: Decimal = months.iter().map(|m| m.expenses).sum();
+
+    Report {
+        from,
+        to,
+        months,
+        categories,
+        average_income: average(total_income),
+        average_expenses: average(total_expenses),
+        average_net: average(total_income + total_expenses),
+    }
+}
+
+/// A past or planned transaction on its date
+pub(crate) struct Occurrence {
+    /// ID of the recurring or one-time transaction
+    pub id: Uuid,
+    pub date: NaiveDate,
+    pub amount: Decimal,
+    pub category: Option<String>,
+}
+
+/// All transactions between `from` and `to` (inclusive), recurring ones only since they
+/// were created
+/// Disabled recurring transactions still count before `today`, it is unknown when exactly
+/// they were disabled, but their past occurrences did happen
+pub(crate) fn occurrences(
+    data: &CashflowData,
+    from: NaiveDate,
+    to: NaiveDate,
+    today: NaiveDate,
+) -> Vec<Occurrence> {
+    let mut result = Vec::new();
+
+    for recurring in &data.recurring {
+        let end = if recurring.active {
+            to
+        } else {
+            to.min(today - Duration::days(1))
+        };
+        // The generator's start is exclusive
+        let start = from.max(recurring.created_at.date_naive()) - Duration::days(1);
+        for (date, txn, _) in generate_recurring_transactions(recurring, start, end) {
+            result.push(Occurrence {
+                id: recurring.id,
+                date,
+                amount: txn.amount,
+                category: txn.category,
+            });
+        }
+    }
+
+    for txn in &data.one_time {
+        if txn.date >= from && txn.date <= to {
+            result.push(Occurrence {
+                id: txn.id,
+                date: txn.date,
+                amount: txn.amount,
+                category: txn.category.clone(),
+            });
+        }
+    }
+
+    result.sort_by_key(|o| o.date);
+    result
+}
+
+/// Earliest date the data has any history for; months before it count as unknown
+fn earliest_history(data: &CashflowData) -> NaiveDate {
+    data.recurring
+        .iter()
+        .map(|r| r.created_at.date_naive())
+        .chain(data.one_time.iter().map(|t| t.date))
+        .min()
+        .unwrap_or(NaiveDate::MAX)
+}
+
+fn add_amount(income: &mut Decimal, expenses: &mut Decimal, amount: Decimal) {
+    if amount.is_sign_negative() {
+        *expenses += amount;
+    } else {
+        *income += amount;
+    }
+}
+
+fn first_day(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+fn last_day(date: NaiveDate) -> NaiveDate {
+    first_day(date) + Months::new(1) - Duration::days(1)
+}
+
+fn months_between(from: NaiveDate, to: NaiveDate) -> u32 {
+    ((to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32 + 1).max(1) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{OneTimeTransaction, RecurringTransaction};
+    use chrono::{TimeZone, Utc};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_build_report() {
+        let mut data = CashflowData::default();
+
+        let mut salary = RecurringTransaction::new("Výplata".to_string(), Decimal::from(40000), 10);
+        salary.created_at = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        salary.category = Some("Příjem".to_string());
+        data.recurring.push(salary);
+
+        let mut rent = RecurringTransaction::new("Nájem".to_string(), Decimal::from(-15000), 1);
+        rent.created_at = Utc.with_ymd_and_hms(2025, 3, 1, 0, 0, 0).unwrap();
+        rent.category = Some("Bydlení".to_string());
+        data.recurring.push(rent);
+
+        data.one_time.push(OneTimeTransaction::new(
+            "Dovolená".to_string(),
+            Decimal::from(-20000),
+            date(2025, 8, 5),
+        ));
+
+        let report = build_report(
+            &data,
+            date(2025, 7, 1),
+            date(2025, 9, 30),
+            date(2025, 9, 15),
+        );
+
+        assert_eq!(report.months.len(), 3);
+        let august = &report.months[1];
+        assert_eq!(august.income, Decimal::from(40000));
+        assert_eq!(august.expenses, Decimal::from(-35000));
+        assert_eq!(august.net, Decimal::from(5000));
+        // A year earlier only the salary existed
+        assert_eq!(august.net_year_ago, Some(Decimal::from(40000)));
+        assert!(!august.projected);
+        assert!(report.months[2].projected);
+
+        let housing = report
+            .categories
+            .iter()
+            .find(|c| c.category == "Bydlení")
+            .unwrap();
+        assert_eq!(housing.expenses, Decimal::from(-45000));
+        assert_eq!(housing.monthly_average, Decimal::from(-15000));
+        assert!(
+            report
+                .categories
+                .iter()
+                .any(|c| c.category == UNCATEGORIZED)
+        );
+
+        // (3 × 40 000 − 3 × 15 000 − 20 000) / 3
+        assert_eq!(
+            report.average_net,
+            Decimal::from_str_exact("18333.33").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_report_keeps_history_of_disabled_transactions() {
+        let mut data = CashflowData::default();
+        let mut gym = RecurringTransaction::new("Posilovna".to_string(), Decimal::from(-1000), 5);
+        gym.created_at = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        gym.active = false;
+        data.recurring.push(gym);
+
+        let report = build_report(
+            &data,
+            date(2025, 7, 1),
+            date(2025, 10, 31),
+            date(2025, 9, 3),
+        );
+        let expenses: Vec<_> = report.months.iter().map(|m| m.expenses).collect();
+
+        // Past months keep the payments, nothing is projected from today on
+        assert_eq!(
+            expenses,
+            vec![
+                Decimal::from(-1000),
+                Decimal::from(-1000),
+                Decimal::ZERO,
+                Decimal::ZERO
+            ]
+        );
+    }
+}
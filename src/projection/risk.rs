@@ -0,0 +1,271 @@
+use crate::models::{CashflowData, OccurrenceStatus, RecurringTransaction};
+use crate::projection::CashflowProjection;
+use chrono::{Duration, NaiveDate};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Percentiles of the minimum balance reported by the simulation
+pub const RISK_PERCENTILES: [u8; 5] = [5, 25, 50, 75, 95];
+
+/// Settings of a Monte Carlo risk simulation
+#[derive(Debug, Clone)]
+pub struct RiskOptions {
+    /// Number of simulated projections
+    pub runs: usize,
+    /// Recurring payments move randomly by up to this many days in either direction
+    pub jitter_days: i64,
+    /// Seed of the random generator; the same seed gives the same result
+    pub seed: u64,
+    /// Balance the user wants to stay above
+    pub threshold: Decimal,
+}
+
+/// Summary of a Monte Carlo risk simulation
+#[derive(Debug, Clone, PartialEq)]
+pub struct RiskReport {
+    pub runs: usize,
+    /// Minimum balance at each of `RISK_PERCENTILES`
+    pub min_balance_percentiles: Vec<(u8, Decimal)>,
+    /// Share of runs in which the balance dropped below zero (0.0 - 1.0)
+    pub probability_below_zero: f64,
+    /// Share of runs in which the balance dropped below the threshold (0.0 - 1.0)
+    pub probability_below_threshold: f64,
+}
+
+/// Runs many randomized variants of the projection in parallel
+/// Variable amounts are sampled from a triangular distribution peaking at the expected
+/// amount; every run has its own generator derived from the seed, so the result does not
+/// depend on the number of threads
+pub fn simulate_risk(
+    data: &CashflowData,
+    projection: &CashflowProjection,
+    options: &RiskOptions,
+) -> RiskReport {
+    let recurring: HashMap<Uuid, &RecurringTransaction> =
+        data.recurring.iter().map(|r| (r.id, r)).collect();
+
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(options.runs.max(1));
+    let chunk_size = options.runs.div_ceil(threads).max(1);
+
+    let mut minimums: Vec<Decimal> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..options.runs)
+            .step_by(chunk_size)
+            .map(|first_run| {
+                let recurring = &recurring;
+                scope.spawn(move || {
+                    (first_run..(first_run + chunk_size).min(options.runs))
+                        .map(|run| simulate_run(projection, recurring, options, run))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("risk simulation thread panicked"))
+            .collect()
+    });
+    minimums.sort();
+
+    let share = |limit: Decimal| {
+        if minimums.is_empty() {
+            return 0.0;
+        }
+        minimums.iter().filter(|m| **m < limit).count() as f64 / minimums.len() as f64
+    };
+
+    RiskReport {
+        runs: minimums.len(),
+        min_balance_percentiles: RISK_PERCENTILES
+            .iter()
+            .map(|&p| (p, percentile(&minimums, p)))
+            .collect(),
+        probability_below_zero: share(Decimal::ZERO),
+        probability_below_threshold: share(options.threshold),
+    }
+}
+
+/// Simulates a single run and returns its minimum balance
+fn simulate_run(
+    projection: &CashflowProjection,
+    recurring: &HashMap<Uuid, &RecurringTransaction>,
+    options: &RiskOptions,
+    run: usize,
+) -> Decimal {
+    let mut rng = ChaCha8Rng::seed_from_u64(options.seed.wrapping_add(run as u64));
+
+    let mut events: Vec<(NaiveDate, Decimal)> = projection
+        .future_txns
+        .iter()
+        .map(|txn| {
+            let Some(template) = recurring.get(&txn.source_id).filter(|_| !txn.is_one_time) else {
+                return (txn.date, txn.amount);
+            };
+
+            // A paid occurrence is settled and a one-off amount is exact; a paid or moved
+            // occurrence also keeps its date
+            let occurrence = template.override_on(txn.date);
+            let confirmed = occurrence.is_some_and(|o| o.status == OccurrenceStatus::Confirmed);
+            let fixed_amount = confirmed || occurrence.is_some_and(|o| o.amount.is_some());
+            let fixed_date = confirmed || occurrence.is_some_and(|o| o.moved_to.is_some());
+
+            let amount = match template.range_on(txn.date) {
+                Some(range) if !fixed_amount => {
+                    sample_triangular(&mut rng, range.min, txn.amount, range.max)
+                }
+                _ => txn.amount,
+            };
+            let date = if options.jitter_days > 0 && !fixed_date {
+                let shift = rng.random_range(-options.jitter_days..=options.jitter_days);
+                (txn.date + Duration::days(shift)).max(projection.start_date)
+            } else {
+                txn.date
+            };
+
+            (date, amount)
+        })
+        .collect();
+    events.sort_by_key(|(date, _)| *date);
+
+    let mut balance = projection.starting_balance;
+    let mut minimum = balance;
+    for (_, amount) in events {
+        balance += amount;
+        minimum = minimum.min(balance);
+    }
+    minimum
+}
+
+/// Samples from a triangular distribution between `min` and `max` peaking at `mode`
+fn sample_triangular(rng: &mut ChaCha8Rng, min: Decimal, mode: Decimal, max: Decimal) -> Decimal {
+    let (Some(a), Some(c), Some(b)) = (min.to_f64(), mode.to_f64(), max.to_f64()) else {
+        return mode;
+    };
+    if b <= a {
+        return mode;
+    }
+    let c = c.clamp(a, b);
+
+    let u: f64 = rng.random();
+    let value = if u < (c - a) / (b - a) {
+        a + (u * (b - a) * (c - a)).sqrt()
+    } else {
+        b - ((1.0 - u) * (b - a) * (b - c)).sqrt()
+    };
+
+    Decimal::from_f64(value).map_or(mode, |v| v.round_dp(2))
+}
+
+/// Nearest-rank percentile of sorted values
+fn percentile(sorted: &[Decimal], p: u8) -> Decimal {
+    if sorted.is_empty() {
+        return Decimal::ZERO;
+    }
+    let rank = (p as usize * sorted.len()).div_ceil(100).max(1);
+    sorted[rank.min(sorted.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AmountRange, BalanceSnapshot};
+    use crate::projection::project_cashflow;
+    use chrono::{Datelike, Local};
+
+    fn data_with_variable_bill() -> CashflowData {
+        let mut data = CashflowData::default();
+        let today = Local::now().date_naive();
+        data.balance_snapshots
+            .push(BalanceSnapshot::new(today, Decimal::from(3000)));
+
+        // Expected -2 000, between -1 000 and -5 000: may push the balance below zero
+        let mut bill = RecurringTransaction::new(
+            "Kreditka".to_string(),
+            Decimal::from(-2000),
+            (today + Duration::days(1)).day() as u8,
+        );
+        bill.amount_range = Some(AmountRange::new(Decimal::from(-1000), Decimal::from(-5000)));
+        data.recurring.push(bill);
+        data
+    }
+
+    #[test]
+    fn test_simulation_is_reproducible() {
+        let data = data_with_variable_bill();
+        let projection = project_cashflow(&data, 20).unwrap();
+        let options = RiskOptions {
+            runs: 500,
+            jitter_days: 2,
+            seed: 42,
+            threshold: Decimal::from(1000),
+        };
+
+        let first = simulate_risk(&data, &projection, &options);
+        let second = simulate_risk(&data, &projection, &options);
+        assert_eq!(first, second);
+        assert_eq!(first.runs, 500);
+
+        // Some but not all runs go negative; the threshold is breached more often
+        assert!(first.probability_below_zero > 0.0 && first.probability_below_zero < 1.0);
+        assert!(first.probability_below_threshold >= first.probability_below_zero);
+
+        // Percentiles are ordered and stay within the possible range
+        let values: Vec<Decimal> = first.min_balance_percentiles.iter().map(|p| p.1).collect();
+        assert!(values.windows(2).all(|w| w[0] <= w[1]));
+        assert!(values[0] >= Decimal::from(-2000));
+        assert!(values[4] <= Decimal::from(2000));
+    }
+
+    #[test]
+    fn test_overridden_occurrence_is_not_sampled() {
+        let mut data = data_with_variable_bill();
+        let date = Local::now().date_naive() + Duration::days(1);
+        let options = RiskOptions {
+            runs: 200,
+            jitter_days: 2,
+            seed: 7,
+            threshold: Decimal::from(1000),
+        };
+
+        // The bill is known to be -2 500 this time: every run ends at the same minimum
+        data.recurring[0].update_occurrence(date, |o| o.amount = Some(Decimal::from(-2500)));
+        let projection = project_cashflow(&data, 20).unwrap();
+        let report = simulate_risk(&data, &projection, &options);
+        assert!(
+            report
+                .min_balance_percentiles
+                .iter()
+                .all(|p| p.1 == Decimal::from(500))
+        );
+
+        // Paid bills are settled at their expected amount
+        data.recurring[0].update_occurrence(date, |o| {
+            o.amount = None;
+            o.status = OccurrenceStatus::Confirmed;
+        });
+        let projection = project_cashflow(&data, 20).unwrap();
+        let report = simulate_risk(&data, &projection, &options);
+        assert_eq!(report.probability_below_zero, 0.0);
+        assert!(
+            report
+                .min_balance_percentiles
+                .iter()
+                .all(|p| p.1 == Decimal::from(1000))
+        );
+    }
+
+    #[test]
+    fn test_percentile() {
+        let values: Vec<Decimal> = (1..=100).map(Decimal::from).collect();
+        assert_eq!(percentile(&values, 5), Decimal::from(5));
+        assert_eq!(percentile(&values, 50), Decimal::from(50));
+        assert_eq!(percentile(&values[..1], 95), Decimal::from(1));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::models::{BalanceSnapshot, CashflowData};
+use crate::projection::{AccountLedger, scheduled_between};
+use chrono::{Duration, NaiveDate};
+use rust_decimal::Decimal;
+
+/// Difference between two consecutive balance snapshots and what the plan expected
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotDiff {
+    pub from_date: NaiveDate,
+    pub from_balance: Decimal,
+    pub to_date: NaiveDate,
+    pub to_balance: Decimal,
+    /// Balance the plan projected for `to_date`, starting from `from_balance`
+    pub expected: Decimal,
+    /// Number of planned transactions between the snapshots
+    pub transactions: usize,
+}
+
+impl SnapshotDiff {
+    /// Actual change between the snapshots
+    pub fn change(&self) -> Decimal {
+        self.to_balance - self.from_balance
+    }
+
+    /// How far reality drifted from the plan, positive means more money than expected
+    pub fn drift(&self) -> Decimal {
+        self.to_balance - self.expected
+    }
+}
+
+/// Compares every pair of consecutive snapshots with the planned transactions between them
+/// Each snapshot's `timing` decides whether the transactions on its day belong before or
+/// after it, the same way the projection does
+pub fn snapshot_diffs(data: &CashflowData) -> Vec<SnapshotDiff> {
+    let mut snapshots: Vec<&BalanceSnapshot> = data.balance_snapshots.iter().collect();
+    snapshots.sort_by_key(|s| s.date);
+
+    snapshots
+        .windows(2)
+        .map(|pair| {
+            let (from, to) = (pair[0], pair[1]);
+            let first_day = from.first_open_day();
+            let last_day = to.first_open_day() - Duration::days(1);
+
+            let scheduled = AccountLedger::new(&data.account).apply(
+                scheduled_between(data, first_day, last_day),
+                from.balance,
+                first_day - Duration::days(1),
+                last_day,
+            );
+
+            SnapshotDiff {
+                from_date: from.date,
+                from_balance: from.balance,
+                to_date: to.date,
+                to_balance: to.balance,
+                expected: from.balance
+                    + scheduled.iter().map(|(_, t, _)| t.amount).sum::<Decimal>(),
+                transactions: scheduled.len(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{OneTimeTransaction, RecurringTransaction};
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_snapshot_diffs() {
+        let mut data = CashflowData::default();
+        // Inserted out of order on purpose
+        data.balance_snapshots
+            .push(BalanceSnapshot::new(date(3, 1), Decimal::from(30500)));
+        data.balance_snapshots
+            .push(BalanceSnapshot::new(date(1, 10), Decimal::from(10000)));
+        data.balance_snapshots
+            .push(BalanceSnapshot::new(date(2, 10), Decimal::from(20000)));
+
+        data.recurring.push(RecurringTransaction::new(
+            "Výplata".to_string(),
+            Decimal::from(25000),
+            15,
+        ));
+        data.one_time.push(OneTimeTransaction::new(
+            "Pojistka".to_string(),
+            Decimal::from(-6000),
+            date(2, 10),
+        ));
+
+        let diffs = snapshot_diffs(&data);
+        assert_eq!(diffs.len(), 2);
+
+        // 10.1. → 10.2.: salary on 15.1., insurance on the day of the later snapshot
+        assert_eq!(diffs[0].from_date, date(1, 10));
+        assert_eq!(diffs[0].expected, Decimal::from(29000));
+        assert_eq!(diffs[0].transactions, 2);
+        assert_eq!(diffs[0].drift(), Decimal::from(-9000));
+
+        // 10.2. → 1.3.: salary on 15.2.
+        assert_eq!(diffs[1].expected, Decimal::from(45000));
+        assert_eq!(diffs[1].change(), Decimal::from(10500));
+        assert_eq!(diffs[1].drift(), Decimal::from(-14500));
+    }
+}
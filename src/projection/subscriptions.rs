@@ -0,0 +1,145 @@
+use crate::models::{CashflowData, normalize_text};
+use crate::projection::generate_recurring_transactions;
+use chrono::{Duration, Months, NaiveDate};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A single active recurring expense as seen by the subscription audit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Subscription {
+    pub id: Uuid,
+    pub description: String,
+    pub category: Option<String>,
+    /// Current monthly cost as a positive number
+    pub monthly_cost: Decimal,
+    pub yearly_cost: Decimal,
+    pub created_at: NaiveDate,
+    /// Next charge from today on (inclusive)
+    pub next_charge: Option<NaiveDate>,
+    /// True if another subscription has the same description
+    pub duplicate: bool,
+}
+
+/// Lists active recurring expenses sorted by cost, most expensive first
+/// Loans are left out: they are paid off on schedule rather than forgotten
+pub fn audit_subscriptions(data: &CashflowData, today: NaiveDate) -> Vec<Subscription> {
+    let mut subscriptions: Vec<Subscription> = data
+        .recurring
+        .iter()
+        .filter(|r| r.active && r.installment_plan.is_none())
+        .filter(|r| r.amount_on(today).is_sign_negative())
+        .map(|r| {
+            let monthly_cost = -r.amount_on(today);
+            let next_charge = generate_recurring_transactions(
+                r,
+                today - Duration::days(1),
+                today + Months::new(1),
+            )
+            .first()
+            .map(|(date, _, _)| *date);
+
+            Subscription {
+                id: r.id,
+                description: r.description.clone(),
+                category: r.category.clone(),
+                monthly_cost,
+                yearly_cost: monthly_cost * Decimal::from(12),
+                created_at: r.created_at.date_naive(),
+                next_charge,
+                duplicate: false,
+            }
+        })
+        .collect();
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for subscription in &subscriptions {
+        *counts
+            .entry(normalize_description(&subscription.description))
+            .or_default() += 1;
+    }
+    for subscription in &mut subscriptions {
+        subscription.duplicate = counts[&normalize_description(&subscription.description)] > 1;
+    }
+
+    subscriptions.sort_by(|a, b| {
+        b.monthly_cost
+            .cmp(&a.monthly_cost)
+            .then_with(|| a.description.cmp(&b.description))
+    });
+    subscriptions
+}
+
+/// Lowercase description without diacritics, punctuation and extra whitespace, so that
+/// "Netflix" and "netflix." or "Nájem" and "Najem" are recognised as the same thing
+fn normalize_description(description: &str) -> String {
+    normalize_text(description)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::RecurringTransaction;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_audit_subscriptions() {
+        let mut data = CashflowData::default();
+        data.recurring.push(RecurringTransaction::new(
+            "Netflix".to_string(),
+            Decimal::from(-199),
+            5,
+        ));
+        data.recurring.push(RecurringTransaction::new(
+            "netflix ".to_string(),
+            Decimal::from(-199),
+            20,
+        ));
+        data.recurring.push(RecurringTransaction::new(
+            "Nájem".to_string(),
+            Decimal::from(-15000),
+            1,
+        ));
+        data.recurring.push(RecurringTransaction::new(
+            "Najem".to_string(),
+            Decimal::from(-15000),
+            1,
+        ));
+        data.recurring.push(RecurringTransaction::new(
+            "Výplata".to_string(),
+            Decimal::from(40000),
+            10,
+        ));
+        let mut disabled = RecurringTransaction::new("Spotify".to_string(), Decimal::from(-169), 1);
+        disabled.active = false;
+        data.recurring.push(disabled);
+
+        let audit = audit_subscriptions(&data, date(2025, 3, 10));
+
+        let names: Vec<&str> = audit.iter().map(|s| s.description.as_str()).collect();
+        assert_eq!(names, vec!["Najem", "Nájem", "Netflix", "netflix "]);
+        assert_eq!(audit[0].yearly_cost, Decimal::from(180000));
+        assert_eq!(audit[0].next_charge, Some(date(2025, 4, 1)));
+        assert_eq!(audit[3].next_charge, Some(date(2025, 3, 20)));
+        // Descriptions differing only in case, punctuation or diacritics are duplicates
+        assert!(audit[0].duplicate && audit[1].duplicate);
+        assert!(audit[2].duplicate && audit[3].duplicate);
+    }
+
+    #[test]
+    fn test_normalize_description() {
+        assert_eq!(
+            normalize_description("  Netflix  Premium. "),
+            "netflix premium"
+        );
+        assert_eq!(normalize_description("O2 - tarif"), "o2 tarif");
+        assert_eq!(normalize_description("Elektřina"), "elektrina");
+    }
+}
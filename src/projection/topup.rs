@@ -0,0 +1,194 @@
+use crate::projection::CashflowProjection;
+use chrono::{Datelike, Duration, NaiveDate};
+use rust_decimal::Decimal;
+
+/// Transfer needed to keep the balance above a threshold
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopUp {
+    /// Latest date the money has to arrive (the day before the balance would drop)
+    pub date: NaiveDate,
+    pub amount: Decimal,
+    /// Lowest balance the transfer covers (without this transfer), with its date
+    pub lowest_balance: Decimal,
+    pub lowest_balance_date: NaiveDate,
+}
+
+/// Computes the smallest single transfer that keeps the balance at or above the threshold
+/// over the whole projection, or None if the balance never drops below it
+pub fn required_topup(projection: &CashflowProjection, threshold: Decimal) -> Option<TopUp> {
+    let points = balance_points(projection);
+
+    let first_breach = points.iter().find(|(_, balance)| *balance < threshold)?;
+    let (lowest_date, lowest) = points
+        .iter()
+        .min_by_key(|(_, balance)| *balance)
+        .copied()
+        .unwrap_or(*first_breach);
+
+    Some(TopUp {
+        date: arrival_date(first_breach.0, projection.start_date),
+        amount: threshold - lowest,
+        lowest_balance: lowest,
+        lowest_balance_date: lowest_date,
+    })
+}
+
+/// Computes a schedule of at most one transfer per month, each as small and as late as
+/// possible, that keeps the balance at or above the threshold
+pub fn monthly_topup_schedule(projection: &CashflowProjection, threshold: Decimal) -> Vec<TopUp> {
+    let points = balance_points(projection);
+    let mut schedule = Vec::new();
+    let mut transferred = Decimal::ZERO;
+
+    let mut index = 0;
+    while index < points.len() {
+        let month = (points[index].0.year(), points[index].0.month());
+        let month_points: Vec<_> = points[index..]
+            .iter()
+            .take_while(|(date, _)| (date.year(), date.month()) == month)
+            .copied()
+            .collect();
+        index += month_points.len();
+
+        // Balances as they would be with the transfers scheduled so far
+        let first_breach = month_points
+            .iter()
+            .find(|(_, balance)| *balance + transferred < threshold);
+        let lowest = month_points.iter().min_by_key(|(_, balance)| *balance);
+
+        if let (Some(&(breach_date, _)), Some(&(lowest_date, lowest))) = (first_breach, lowest) {
+            let amount = threshold - (lowest + transferred);
+            schedule.push(TopUp {
+                date: arrival_date(breach_date, projection.start_date),
+                amount,
+                lowest_balance: lowest + transferred,
+                lowest_balance_date: lowest_date,
+            });
+            transferred += amount;
+        }
+    }
+
+    schedule
+}
+
+/// Today's balance followed by the balance at the end of each day with projected
+/// transactions
+/// Same-day entries are ordered by creation, so a top-up created for today would come
+/// after today's payments; only the end-of-day balance is independent of that order
+fn balance_points(projection: &CashflowProjection) -> Vec<(NaiveDate, Decimal)> {
+    let mut points: Vec<(NaiveDate, Decimal)> = Vec::new();
+    let balances = std::iter::once((projection.start_date, projection.starting_balance)).chain(
+        projection
+            .future_txns
+            .iter()
+            .map(|t| (t.date, t.balance_after)),
+    );
+    for (date, balance) in balances {
+        match points.last_mut() {
+            Some(last) if last.0 == date => last.1 = balance,
+            _ => points.push((date, balance)),
+        }
+    }
+    points
+}
+
+/// Money must arrive the day before the breach, but not earlier than today
+fn arrival_date(breach_date: NaiveDate, today: NaiveDate) -> NaiveDate {
+    (breach_date - Duration::days(1)).max(today)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BalanceSnapshot, CashflowData, OneTimeTransaction, TransactionView};
+    use crate::projection::project_cashflow;
+    use chrono::Local;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap()
+    }
+
+    fn txn(date: NaiveDate, amount: i64, balance_after: i64) -> TransactionView {
+        TransactionView {
+            source_id: uuid::Uuid::new_v4(),
+            date,
+            day_of_month: date.day() as u8,
+            description: "Test".to_string(),
+            amount: Decimal::from(amount),
+            category: None,
+            is_one_time: false,
+            is_generated: false,
+            balance_after: Decimal::from(balance_after),
+            worst_amount: Decimal::from(amount),
+            worst_balance_after: Decimal::from(balance_after),
+            confirmed: false,
+        }
+    }
+
+    #[test]
+    fn test_topup() {
+        let snapshot = BalanceSnapshot::new(date(10, 1), Decimal::from(12000));
+        let projection = CashflowProjection {
+            starting_balance: Decimal::from(12000),
+            start_date: date(10, 1),
+            balance_snapshot: &snapshot,
+            snapshot_age_days: 0,
+            future_txns: vec![
+                txn(date(10, 5), -4000, 8000),
+                txn(date(10, 20), -2000, 6000),
+                txn(date(11, 1), 3000, 9000),
+                txn(date(11, 10), -4000, 5000),
+            ],
+            past_txns: Vec::new(),
+        };
+        let threshold = Decimal::from(10000);
+
+        // One transfer: cover the lowest point, arriving before the first breach
+        let topup = required_topup(&projection, threshold).unwrap();
+        assert_eq!(topup.amount, Decimal::from(5000));
+        assert_eq!(topup.date, date(10, 4));
+        assert_eq!(topup.lowest_balance_date, date(11, 10));
+
+        // Monthly: October needs 4 000, November the remaining 1 000
+        let schedule = monthly_topup_schedule(&projection, threshold);
+        assert_eq!(schedule.len(), 2);
+        assert_eq!(schedule[0].amount, Decimal::from(4000));
+        assert_eq!(schedule[0].date, date(10, 4));
+        assert_eq!(schedule[1].amount, Decimal::from(1000));
+        assert_eq!(schedule[1].date, date(11, 9));
+
+        // Nothing needed below a lower threshold
+        assert!(required_topup(&projection, Decimal::from(1000)).is_none());
+    }
+
+    #[test]
+    fn test_topup_for_today() {
+        let today = Local::now().date_naive();
+        let mut data = CashflowData::default();
+        data.balance_snapshots.push(BalanceSnapshot::new(
+            today - Duration::days(1),
+            Decimal::from(12000),
+        ));
+        data.one_time.push(OneTimeTransaction::new(
+            "Pojistka".to_string(),
+            Decimal::from(-4000),
+            today,
+        ));
+        let threshold = Decimal::from(10000);
+
+        let projection = project_cashflow(&data, 30).unwrap();
+        let topup = required_topup(&projection, threshold).unwrap();
+        assert_eq!(topup.date, today);
+        assert_eq!(topup.amount, Decimal::from(2000));
+
+        // Like `topup --create`; the transfer sorts after today's payment, yet covers it
+        data.one_time.push(OneTimeTransaction::new(
+            "Top-up".to_string(),
+            topup.amount,
+            topup.date,
+        ));
+        let projection = project_cashflow(&data, 30).unwrap();
+        assert!(required_topup(&projection, threshold).is_none());
+        assert!(monthly_topup_schedule(&projection, threshold).is_empty());
+    }
+}
@@ -0,0 +1,7 @@
+mod error;
+mod handlers;
+mod router;
+
+pub use error::*;
+pub use handlers::*;
+pub use router::*;
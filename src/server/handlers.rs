@@ -0,0 +1,455 @@
+use crate::commands::{RecurringChanges, apply_recurring_changes, collect_metrics, parse_category};
+use crate::models::{
+    BalanceSnapshot, OneTimeTransaction, RecurringTransaction, SnapshotTiming, TransactionView,
+};
+use crate::projection::project_cashflow;
+use crate::server::{ApiError, ApiState};
+use crate::storage::{load_data, save_data};
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use chrono::{Local, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+type ApiResult<T> = Result<T, ApiError>;
+
+pub async fn health() -> Json<Value> {
+    Json(json!({ "status": "ok" }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PlanQuery {
+    /// Number of days to look ahead (default: 30)
+    pub days: Option<i64>,
+}
+
+/// Projection as returned by `GET /api/plan`
+#[derive(Debug, Serialize)]
+pub struct PlanResponse {
+    pub snapshot_date: NaiveDate,
+    pub snapshot_balance: Decimal,
+    /// Days since the snapshot, the starting balance is an estimate when this is large
+    pub snapshot_age_days: i64,
+    pub start_date: NaiveDate,
+    pub starting_balance: Decimal,
+    pub ending_balance: Decimal,
+    pub min_balance: Decimal,
+    pub min_balance_date: NaiveDate,
+    /// Transactions between the snapshot and today, already reflected in `starting_balance`
+    pub past: Vec<TransactionView>,
+    pub transactions: Vec<TransactionView>,
+}
+
+pub async fn get_plan(Query(query): Query<PlanQuery>) -> ApiResult<Json<PlanResponse>> {
+    let days = query.days.unwrap_or(30);
+    if !(1..=3660).contains(&days) {
+        return Err(ApiError::bad_request("days must be between 1 and 3660"));
+    }
+
+    let data = load_data().await?;
+    if data.balance_snapshots.is_empty() {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "No balance snapshot found. Set the balance first.",
+        ));
+    }
+
+    let projection = project_cashflow(&data, days)?;
+    let (min_balance, min_balance_date) = projection.min_balance();
+
+    Ok(Json(PlanResponse {
+        snapshot_date: projection.balance_snapshot.date,
+        snapshot_balance: projection.balance_snapshot.balance,
+        snapshot_age_days: projection.snapshot_age_days,
+        start_date: projection.start_date,
+        starting_balance: projection.starting_balance,
+        ending_balance: projection.ending_balance(),
+        min_balance,
+        min_balance_date,
+        past: projection.past_txns,
+        transactions: projection.future_txns,
+    }))
+}
+
+/// Prometheus metrics, same as `cashflow metrics`
+pub async fn get_metrics(Query(query): Query<PlanQuery>) -> ApiResult<impl IntoResponse> {
+    let days = query.days.unwrap_or(90);
+    if !(1..=3660).contains(&days) {
+        return Err(ApiError::bad_request("days must be between 1 and 3660"));
+    }
+
+    let metrics = collect_metrics(days).await?;
+    Ok((
+        [(CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        metrics,
+    ))
+}
+
+/// Body of `POST /api/recurring`
+#[derive(Debug, Deserialize)]
+pub struct NewRecurring {
+    pub description: String,
+    pub amount: Decimal,
+    pub day_of_month: u8,
+    pub category: Option<String>,
+}
+
+/// Body of `PATCH /api/recurring/{id}`; missing fields are kept
+#[derive(Debug, Default, Deserialize)]
+pub struct RecurringPatch {
+    pub description: Option<String>,
+    pub amount: Option<Decimal>,
+    /// Date the new amount takes effect (default today), like `recurring edit --effective`
+    pub effective_from: Option<NaiveDate>,
+    /// Replace the amount for past occurrences too, like `recurring edit --rewrite`
+    #[serde(default)]
+    pub rewrite: bool,
+    pub day_of_month: Option<u8>,
+    pub min: Option<Decimal>,
+    pub max: Option<Decimal>,
+    /// Drop the amount range
+    #[serde(default)]
+    pub no_range: bool,
+    /// New category, empty to remove
+    pub category: Option<String>,
+    pub active: Option<bool>,
+}
+
+impl RecurringPatch {
+    /// Applies the changes with the same code as `recurring edit`
+    pub fn apply(self, txn: &mut RecurringTransaction, today: NaiveDate) -> ApiResult<()> {
+        let changes = RecurringChanges {
+            amount: self.amount,
+            effective_from: self.effective_from,
+            rewrite: self.rewrite,
+            day_of_month: self.day_of_month,
+            description: self.description,
+            min: self.min,
+            max: self.max,
+            no_range: self.no_range,
+            category: self.category,
+        };
+        apply_recurring_changes(txn, changes, today)
+            .map_err(|error| ApiError::bad_request(format!("{:#}", error)))?;
+        if let Some(active) = self.active {
+            txn.active = active;
+        }
+        Ok(())
+    }
+}
+
+pub async fn list_recurring() -> ApiResult<Json<Vec<RecurringTransaction>>> {
+    Ok(Json(load_data().await?.recurring))
+}
+
+pub async fn get_recurring(Path(id): Path<Uuid>) -> ApiResult<Json<RecurringTransaction>> {
+    load_data()
+        .await?
+        .recurring
+        .into_iter()
+        .find(|t| t.id == id)
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("Recurring transaction"))
+}
+
+pub async fn create_recurring(
+    State(state): State<ApiState>,
+    Json(body): Json<NewRecurring>,
+) -> ApiResult<(StatusCode, Json<RecurringTransaction>)> {
+    let mut transaction =
+        RecurringTransaction::new(body.description, body.amount, body.day_of_month);
+    transaction.category = parse_category(body.category.as_deref());
+    transaction.validate()?;
+
+    let _guard = state.write_lock.lock().await;
+    let mut data = load_data().await?;
+    data.recurring.push(transaction.clone());
+    save_data(&data).await?;
+
+    Ok((StatusCode::CREATED, Json(transaction)))
+}
+
+pub async fn update_recurring(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+    Json(patch): Json<RecurringPatch>,
+) -> ApiResult<Json<RecurringTransaction>> {
+    let _guard = state.write_lock.lock().await;
+    let mut data = load_data().await?;
+
+    let transaction = data
+        .recurring
+        .iter_mut()
+        .find(|t| t.id == id)
+        .ok_or_else(|| ApiError::not_found("Recurring transaction"))?;
+    patch.apply(transaction, Local::now().date_naive())?;
+    transaction.validate()?;
+
+    let transaction = transaction.clone();
+    save_data(&data).await?;
+
+    Ok(Json(transaction))
+}
+
+pub async fn delete_recurring(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let _guard = state.write_lock.lock().await;
+    let mut data = load_data().await?;
+
+    let before = data.recurring.len();
+    data.recurring.retain(|t| t.id != id);
+    if data.recurring.len() == before {
+        return Err(ApiError::not_found("Recurring transaction"));
+    }
+    save_data(&data).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Body of `POST /api/one-time`
+#[derive(Debug, Deserialize)]
+pub struct NewOneTime {
+    pub description: String,
+    pub amount: Decimal,
+    pub date: NaiveDate,
+    pub category: Option<String>,
+}
+
+/// Body of `PATCH /api/one-time/{id}`; missing fields are kept
+#[derive(Debug, Default, Deserialize)]
+pub struct OneTimePatch {
+    pub description: Option<String>,
+    pub amount: Option<Decimal>,
+    pub date: Option<NaiveDate>,
+    /// New category, empty to remove
+    pub category: Option<String>,
+}
+
+impl OneTimePatch {
+    pub fn apply(self, txn: &mut OneTimeTransaction) {
+        if let Some(description) = self.description {
+            txn.description = description;
+        }
+        if let Some(amount) = self.amount {
+            txn.amount = amount;
+        }
+        if let Some(date) = self.date {
+            txn.date = date;
+        }
+        if let Some(category) = self.category {
+            txn.category = parse_category(Some(&category));
+        }
+    }
+}
+
+pub async fn list_one_time() -> ApiResult<Json<Vec<OneTimeTransaction>>> {
+    Ok(Json(load_data().await?.one_time))
+}
+
+pub async fn get_one_time(Path(id): Path<Uuid>) -> ApiResult<Json<OneTimeTransaction>> {
+    load_data()
+        .await?
+        .one_time
+        .into_iter()
+        .find(|t| t.id == id)
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("One-time transaction"))
+}
+
+pub async fn create_one_time(
+    State(state): State<ApiState>,
+    Json(body): Json<NewOneTime>,
+) -> ApiResult<(StatusCode, Json<OneTimeTransaction>)> {
+    let mut transaction = OneTimeTransaction::new(body.description, body.amount, body.date);
+    transaction.category = parse_category(body.category.as_deref());
+    transaction.validate()?;
+
+    let _guard = state.write_lock.lock().await;
+    let mut data = load_data().await?;
+    data.one_time.push(transaction.clone());
+    save_data(&data).await?;
+
+    Ok((StatusCode::CREATED, Json(transaction)))
+}
+
+pub async fn update_one_time(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+    Json(patch): Json<OneTimePatch>,
+) -> ApiResult<Json<OneTimeTransaction>> {
+    let _guard = state.write_lock.lock().await;
+    let mut data = load_data().await?;
+
+    let transaction = data
+        .one_time
+        .iter_mut()
+        .find(|t| t.id == id)
+        .ok_or_else(|| ApiError::not_found("One-time transaction"))?;
+    patch.apply(transaction);
+    transaction.validate()?;
+
+    let transaction = transaction.clone();
+    save_data(&data).await?;
+
+    Ok(Json(transaction))
+}
+
+pub async fn delete_one_time(
+    State(state): State<ApiState>,
+    Path(id): Path<Uuid>,
+) -> ApiResult<StatusCode> {
+    let _guard = state.write_lock.lock().await;
+    let mut data = load_data().await?;
+
+    let before = data.one_time.len();
+    data.one_time.retain(|t| t.id != id);
+    if data.one_time.len() == before {
+        return Err(ApiError::not_found("One-time transaction"));
+    }
+    save_data(&data).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Body of `POST /api/balance`
+#[derive(Debug, Deserialize)]
+pub struct NewBalance {
+    pub balance: Decimal,
+    /// Date of the balance (default today)
+    pub date: Option<NaiveDate>,
+    /// `"Before"` or `"After"` the day's transactions (default after)
+    #[serde(default)]
+    pub timing: SnapshotTiming,
+}
+
+/// Balance snapshots, oldest first
+pub async fn list_balance() -> ApiResult<Json<Vec<BalanceSnapshot>>> {
+    let mut snapshots = load_data().await?.balance_snapshots;
+    snapshots.sort_by_key(|s| s.date);
+    Ok(Json(snapshots))
+}
+
+/// Sets the balance for a date, replacing a snapshot on the same date like `balance set`
+pub async fn set_balance(
+    State(state): State<ApiState>,
+    Json(body): Json<NewBalance>,
+) -> ApiResult<Json<BalanceSnapshot>> {
+    let date = body.date.unwrap_or_else(|| Local::now().date_naive());
+
+    let _guard = state.write_lock.lock().await;
+    let mut data = load_data().await?;
+
+    let snapshot = match data.balance_snapshots.iter_mut().find(|s| s.date == date) {
+        Some(existing) => {
+            existing.balance = body.balance;
+            existing.timing = body.timing;
+            existing.clone()
+        }
+        None => {
+            let mut snapshot = BalanceSnapshot::new(date, body.balance);
+            snapshot.timing = body.timing;
+            data.balance_snapshots.push(snapshot.clone());
+            snapshot
+        }
+    };
+    save_data(&data).await?;
+
+    Ok(Json(snapshot))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::OccurrenceStatus;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_recurring_patch() {
+        let mut txn = RecurringTransaction::new("Netflix".to_string(), Decimal::from(-199), 14);
+        let patch: RecurringPatch = serde_json::from_str(
+            r#"{"amount": "-229", "effective_from": "2025-12-01", "category": "Zábava"}"#,
+        )
+        .unwrap();
+        patch.apply(&mut txn, date(2025, 10, 1)).unwrap();
+
+        // Like `recurring edit`, the new amount only applies from its effective date
+        assert_eq!(txn.amount_on(date(2025, 11, 14)), Decimal::from(-199));
+        assert_eq!(txn.amount_on(date(2025, 12, 14)), Decimal::from(-229));
+        assert_eq!(txn.category.as_deref(), Some("Zábava"));
+        assert_eq!(txn.day_of_month, 14);
+
+        let patch: RecurringPatch =
+            serde_json::from_str(r#"{"category": "", "active": false}"#).unwrap();
+        patch.apply(&mut txn, date(2025, 10, 1)).unwrap();
+        assert_eq!(txn.category, None);
+        assert!(!txn.active);
+    }
+
+    #[test]
+    fn test_recurring_patch_day_keeps_overrides() {
+        let mut rent = RecurringTransaction::new("Nájem".to_string(), Decimal::from(-5000), 20);
+        rent.update_occurrence(date(2025, 11, 20), |o| o.status = OccurrenceStatus::Skipped);
+        rent.update_occurrence(date(2025, 12, 20), |o| {
+            o.amount = Some(Decimal::from(-5500))
+        });
+
+        let patch: RecurringPatch = serde_json::from_str(r#"{"day_of_month": 25}"#).unwrap();
+        patch.apply(&mut rent, date(2025, 10, 1)).unwrap();
+
+        // Like `recurring edit`, the overrides move along to the new day
+        let skipped = rent.occurrence_override(date(2025, 11, 25)).unwrap();
+        assert_eq!(skipped.status, OccurrenceStatus::Skipped);
+        assert_eq!(
+            rent.occurrence_amount(date(2025, 12, 25)),
+            Decimal::from(-5500)
+        );
+        assert!(rent.occurrence_override(date(2025, 11, 20)).is_none());
+    }
+
+    #[test]
+    fn test_recurring_patch_range() {
+        let mut power =
+            RecurringTransaction::new("Elektřina".to_string(), Decimal::from(-1500), 15);
+        let patch: RecurringPatch =
+            serde_json::from_str(r#"{"min": "-1800", "max": "-1200"}"#).unwrap();
+        patch.apply(&mut power, date(2025, 10, 1)).unwrap();
+        assert_eq!(
+            power.worst_amount_on(date(2025, 10, 15)),
+            Decimal::from(-1800)
+        );
+
+        // A range that does not contain the amount is rejected
+        let patch: RecurringPatch =
+            serde_json::from_str(r#"{"min": "-1400", "max": "-1200"}"#).unwrap();
+        let error = patch.apply(&mut power, date(2025, 10, 1)).unwrap_err();
+        assert_eq!(error.status, StatusCode::BAD_REQUEST);
+
+        let patch: RecurringPatch = serde_json::from_str(r#"{"no_range": true}"#).unwrap();
+        patch.apply(&mut power, date(2025, 10, 1)).unwrap();
+        assert_eq!(power.amount_range, None);
+    }
+
+    #[test]
+    fn test_validation_error_status() {
+        let mut txn =
+            OneTimeTransaction::new("Oprava".to_string(), Decimal::from(-500), date(2025, 1, 1));
+        OneTimePatch {
+            amount: Some(Decimal::ZERO),
+            ..Default::default()
+        }
+        .apply(&mut txn);
+
+        let error = ApiError::from(txn.validate().unwrap_err());
+        assert_eq!(error.status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+}
@@ -0,0 +1,128 @@
+use crate::Result;
+use crate::server::{ApiError, handlers};
+use anyhow::Context;
+use axum::Router;
+use axum::extract::{Request, State};
+use axum::http::header::AUTHORIZATION;
+use axum::middleware::{self, Next};
+use axum::response::Response;
+use axum::routing::get;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Shared state of the API server
+#[derive(Clone)]
+pub struct ApiState {
+    /// Bearer token required on `/api` routes, if any
+    token: Option<Arc<str>>,
+    /// Serializes load-modify-save cycles so concurrent writes do not lose changes
+    pub(crate) write_lock: Arc<Mutex<()>>,
+}
+
+impl ApiState {
+    pub fn new(token: Option<String>) -> Self {
+        Self {
+            token: token.map(Arc::from),
+            write_lock: Arc::new(Mutex::new(())),
+        }
+    }
+}
+
+/// Builds the router: `/health` is always open, `/api/*` and `/metrics` require the token
+/// when one is set
+pub fn router(state: ApiState) -> Router {
+    let api = Router::new()
+        .route("/plan", get(handlers::get_plan))
+        .route(
+            "/recurring",
+            get(handlers::list_recurring).post(handlers::create_recurring),
+        )
+        .route(
+            "/recurring/{id}",
+            get(handlers::get_recurring)
+                .patch(handlers::update_recurring)
+                .delete(handlers::delete_recurring),
+        )
+        .route(
+            "/one-time",
+            get(handlers::list_one_time).post(handlers::create_one_time),
+        )
+        .route(
+            "/one-time/{id}",
+            get(handlers::get_one_time)
+                .patch(handlers::update_one_time)
+                .delete(handlers::delete_one_time),
+        )
+        .route(
+            "/balance",
+            get(handlers::list_balance).post(handlers::set_balance),
+        )
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
+    let metrics = Router::new()
+        .route("/metrics", get(handlers::get_metrics))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token));
+
+    Router::new()
+        .route("/health", get(handlers::health))
+        .merge(metrics)
+        .nest("/api", api)
+        .with_state(state)
+}
+
+/// Runs the API server until the process is stopped
+pub async fn serve(bind: SocketAddr, token: Option<String>) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(bind)
+        .await
+        .with_context(|| format!("Failed to bind {}", bind))?;
+
+    axum::serve(listener, router(ApiState::new(token)))
+        .await
+        .context("API server failed")
+}
+
+async fn require_token(
+    State(state): State<ApiState>,
+    request: Request,
+    next: Next,
+) -> std::result::Result<Response, ApiError> {
+    if let Some(token) = &state.token {
+        let header = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok());
+        if !is_authorized(header, token) {
+            return Err(ApiError::unauthorized());
+        }
+    }
+    Ok(next.run(request).await)
+}
+
+/// Checks an `Authorization: Bearer <token>` header
+/// Compares in constant time so the token cannot be guessed byte by byte
+fn is_authorized(header: Option<&str>, token: &str) -> bool {
+    let Some(given) = header.and_then(|h| h.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_authorized() {
+        assert!(is_authorized(Some("Bearer s3cret"), "s3cret"));
+        assert!(!is_authorized(Some("Bearer s3cre"), "s3cret"));
+        assert!(!is_authorized(Some("Bearer s3creT"), "s3cret"));
+        assert!(!is_authorized(Some("s3cret"), "s3cret"));
+        assert!(!is_authorized(None, "s3cret"));
+    }
+}
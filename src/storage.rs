@@ -1,3 +1,7 @@
+mod archive;
+mod migrations;
 mod ron_storage;
 
+pub use archive::*;
+pub use migrations::*;
 pub use ron_storage::*;
@@ -0,0 +1,147 @@
+use crate::Result;
+use crate::models::CashflowData;
+use crate::storage::{ensure_data_dir, get_data_dir, read_ron_file, write_ron_file};
+use chrono::NaiveDate;
+use std::path::PathBuf;
+
+/// Gets the path to the archive file (data_dir/archive.ron)
+/// The archive uses the same format as data.ron but only holds past
+/// one-time transactions and superseded balance snapshots
+pub async fn get_archive_path() -> Result<PathBuf> {
+    Ok(get_data_dir().await?.join("archive.ron"))
+}
+
+/// Loads the archive, returns empty data if it doesn't exist yet
+pub async fn load_archive() -> Result<CashflowData> {
+    read_ron_file(&get_archive_path().await?).await
+}
+
+/// Saves the archive atomically
+pub async fn save_archive(archive: &CashflowData) -> Result<()> {
+    ensure_data_dir().await?;
+    write_ron_file(&get_archive_path().await?, archive).await
+}
+
+/// Loads the data merged with the archive, for commands that look at history
+pub async fn load_data_with_archive() -> Result<CashflowData> {
+    let mut data = crate::storage::load_data().await?;
+    let archive = load_archive().await?;
+
+    data.one_time.extend(archive.one_time);
+    data.balance_snapshots.extend(archive.balance_snapshots);
+
+    Ok(data)
+}
+
+/// Summary of entries moved to the archive
+#[derive(Debug, Default, PartialEq)]
+pub struct PruneSummary {
+    pub one_time: usize,
+    pub balance_snapshots: usize,
+}
+
+impl PruneSummary {
+    pub fn is_empty(&self) -> bool {
+        self.one_time == 0 && self.balance_snapshots == 0
+    }
+}
+
+/// Moves past entries from data to the archive
+/// Only entries strictly older than both `before` and the latest balance snapshot are
+/// moved, because those are already reflected in the snapshot and can't affect projections
+pub fn prune_data(
+    data: &mut CashflowData,
+    archive: &mut CashflowData,
+    before: NaiveDate,
+) -> PruneSummary {
+    let Some(latest_snapshot) = data.balance_snapshots.iter().map(|s| s.date).max() else {
+        return PruneSummary::default();
+    };
+    let cutoff = before.min(latest_snapshot);
+
+    let (pruned, kept) = data
+        .one_time
+        .drain(..)
+        .partition::<Vec<_>, _>(|t| t.date < cutoff);
+    data.one_time = kept;
+
+    let (pruned_snapshots, kept_snapshots) = data
+        .balance_snapshots
+        .drain(..)
+        .partition::<Vec<_>, _>(|s| s.date < cutoff);
+    data.balance_snapshots = kept_snapshots;
+
+    let summary = PruneSummary {
+        one_time: pruned.len(),
+        balance_snapshots: pruned_snapshots.len(),
+    };
+
+    archive.one_time.extend(pruned);
+    archive.one_time.sort_by_key(|t| t.date);
+    archive.balance_snapshots.extend(pruned_snapshots);
+    archive.balance_snapshots.sort_by_key(|s| s.date);
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BalanceSnapshot, OneTimeTransaction};
+    use rust_decimal::Decimal;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 10, day).unwrap()
+    }
+
+    #[test]
+    fn test_prune_data() {
+        let mut data = CashflowData::default();
+        let mut archive = CashflowData::default();
+
+        data.balance_snapshots
+            .push(BalanceSnapshot::new(date(1), Decimal::from(1000)));
+        data.balance_snapshots
+            .push(BalanceSnapshot::new(date(10), Decimal::from(2000)));
+
+        for day in [5, 10, 20] {
+            data.one_time.push(OneTimeTransaction::new(
+                format!("Day {}", day),
+                Decimal::from(-100),
+                date(day),
+            ));
+        }
+
+        // `before` later than the latest snapshot is capped at the snapshot date
+        let summary = prune_data(&mut data, &mut archive, date(25));
+
+        assert_eq!(
+            summary,
+            PruneSummary {
+                one_time: 1,
+                balance_snapshots: 1,
+            }
+        );
+        assert_eq!(data.one_time.len(), 2);
+        assert_eq!(data.balance_snapshots.len(), 1);
+        assert_eq!(data.balance_snapshots[0].date, date(10));
+        assert_eq!(archive.one_time[0].date, date(5));
+        assert_eq!(archive.balance_snapshots[0].date, date(1));
+    }
+
+    #[test]
+    fn test_prune_without_snapshot_does_nothing() {
+        let mut data = CashflowData::default();
+        let mut archive = CashflowData::default();
+        data.one_time.push(OneTimeTransaction::new(
+            "Old".to_string(),
+            Decimal::from(-100),
+            date(1),
+        ));
+
+        let summary = prune_data(&mut data, &mut archive, date(25));
+
+        assert!(summary.is_empty());
+        assert_eq!(data.one_time.len(), 1);
+    }
+}
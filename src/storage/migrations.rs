@@ -0,0 +1,606 @@
+use crate::Result;
+use crate::models::{CashflowData, SCHEMA_VERSION};
+use anyhow::{Context, anyhow};
+use chrono::{DateTime, Utc};
+use ron::value::RawValue;
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use std::fmt;
+
+/// Schema version assumed for files written before versioning existed
+const UNVERSIONED: u32 = 0;
+
+/// A single migration step operating on the raw RON document
+type Migration = fn(&mut RawStruct) -> Result<()>;
+
+/// Ordered migration steps: `MIGRATIONS[n]` upgrades a version `n` file to version `n + 1`
+const MIGRATIONS: &[Migration] = &[
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+    migrate_v4_to_v5,
+    migrate_v5_to_v6,
+    migrate_v6_to_v7,
+    migrate_v7_to_v8,
+    migrate_v8_to_v9,
+    migrate_v9_to_v10,
+    migrate_v10_to_v11,
+    migrate_v11_to_v12,
+];
+
+/// Result of parsing a data file, possibly upgraded from an older schema
+#[derive(Debug)]
+pub struct ParsedData {
+    pub data: CashflowData,
+    /// Schema version the file was written with
+    pub original_version: u32,
+}
+
+impl ParsedData {
+    /// True if the file was upgraded and should be written back
+    pub fn was_migrated(&self) -> bool {
+        self.original_version < SCHEMA_VERSION
+    }
+}
+
+/// Parses the contents of a data file, migrating older schema versions step by step
+/// Refuses files written by a newer version of cashflow
+pub fn parse_data(contents: &str) -> Result<ParsedData> {
+    let mut document = RawStruct::parse(contents).context("Failed to parse RON data file")?;
+    let version = read_schema_version(&document)?;
+
+    if version > SCHEMA_VERSION {
+        anyhow::bail!(
+            "Data file uses schema version {} but this cashflow build only supports up to version {}. Please upgrade cashflow.",
+            version,
+            SCHEMA_VERSION
+        );
+    }
+
+    if version == SCHEMA_VERSION {
+        // Parse directly from the text to keep line/column information in errors
+        let data: CashflowData =
+            ron::from_str(contents).context("Failed to parse RON data file")?;
+        return Ok(ParsedData {
+            data,
+            original_version: version,
+        });
+    }
+
+    for (step, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        migration(&mut document)
+            .with_context(|| format!("Failed to migrate data from version {}", step))?;
+        document.set("schema_version", (step + 1).to_string());
+    }
+
+    let data: CashflowData =
+        ron::from_str(&document.to_ron()).context("Failed to load migrated data")?;
+
+    Ok(ParsedData {
+        data,
+        original_version: version,
+    })
+}
+
+/// Reads `schema_version` from the raw document, missing means unversioned
+fn read_schema_version(document: &RawStruct) -> Result<u32> {
+    match document.get("schema_version") {
+        None => Ok(UNVERSIONED),
+        Some(value) => ron::from_str(value).map_err(|e| anyhow!("Invalid schema_version: {}", e)),
+    }
+}
+
+/// v0 → v1: introduces the `schema_version` field itself (written after every step)
+fn migrate_v0_to_v1(_document: &mut RawStruct) -> Result<()> {
+    Ok(())
+}
+
+/// v1 → v2: adds what-if `scenarios`
+fn migrate_v1_to_v2(document: &mut RawStruct) -> Result<()> {
+    document.insert("scenarios", "[]");
+    Ok(())
+}
+
+/// v2 → v3: adds `amount_changes` to recurring transactions
+fn migrate_v2_to_v3(document: &mut RawStruct) -> Result<()> {
+    insert_into_recurring(document, "amount_changes", "[]")
+}
+
+/// v3 → v4: adds an optional loan `installment_plan` to recurring transactions
+fn migrate_v3_to_v4(document: &mut RawStruct) -> Result<()> {
+    insert_into_recurring(document, "installment_plan", "None")
+}
+
+/// v4 → v5: adds an optional `amount_range` for variable recurring amounts
+fn migrate_v4_to_v5(document: &mut RawStruct) -> Result<()> {
+    insert_into_recurring(document, "amount_range", "None")
+}
+
+/// v5 → v6: adds `account` interest and fee rules, all disabled
+fn migrate_v5_to_v6(document: &mut RawStruct) -> Result<()> {
+    document.insert(
+        "account",
+        r#"(interest_rate: "0", overdraft_rate: "0", interest_day: 31, monthly_fee: "0", fee_day: 1)"#,
+    );
+    Ok(())
+}
+
+/// v6 → v7: adds an optional `category` to recurring and one-time transactions
+fn migrate_v6_to_v7(document: &mut RawStruct) -> Result<()> {
+    insert_into_recurring(document, "category", "None")?;
+    insert_into_one_time(document, "category", "None")
+}
+
+/// v7 → v8: adds per-category `budgets`
+fn migrate_v7_to_v8(document: &mut RawStruct) -> Result<()> {
+    document.insert("budgets", "[]");
+    Ok(())
+}
+
+/// v8 → v9: adds savings `goals` and an optional `end_date` to recurring transactions
+fn migrate_v8_to_v9(document: &mut RawStruct) -> Result<()> {
+    document.insert("goals", "[]");
+    insert_into_recurring(document, "end_date", "None")
+}
+
+/// v9 → v10: adds `timing` to balance snapshots, existing snapshots keep the end-of-day
+/// meaning the projection always used for past snapshots
+fn migrate_v9_to_v10(document: &mut RawStruct) -> Result<()> {
+    document.update_structs("balance_snapshots", |snapshot| {
+        snapshot.insert("timing", "After");
+        Ok(())
+    })
+}
+
+/// v10 → v11: adds per-occurrence `overrides` to recurring transactions
+fn migrate_v10_to_v11(document: &mut RawStruct) -> Result<()> {
+    insert_into_recurring(document, "overrides", "[]")
+}
+
+/// v11 → v12: adds `effective_from` to scenario `Remove` and `ChangeAmount` changes,
+/// dated to the day the scenario was created
+fn migrate_v11_to_v12(document: &mut RawStruct) -> Result<()> {
+    document.update_structs("scenarios", |scenario| {
+        let created_at: DateTime<Utc> = ron::from_str(
+            scenario
+                .get("created_at")
+                .context("Scenario without created_at")?,
+        )
+        .context("Invalid scenario created_at")?;
+        let effective_from = ron::to_string(&created_at.date_naive())?;
+
+        scenario.update_variants("changes", |change| {
+            if change.name == "Remove" || change.name == "ChangeAmount" {
+                change.update_fields(|fields| {
+                    fields.insert("effective_from", &effective_from);
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })
+    })
+}
+
+/// Adds a field to every recurring transaction, including those added by scenarios
+fn insert_into_recurring(document: &mut RawStruct, name: &str, value: &str) -> Result<()> {
+    insert_into_transactions(document, "recurring", "AddRecurring", name, value)
+}
+
+/// Adds a field to every one-time transaction, including those added by scenarios
+fn insert_into_one_time(document: &mut RawStruct, name: &str, value: &str) -> Result<()> {
+    insert_into_transactions(document, "one_time", "AddOneTime", name, value)
+}
+
+fn insert_into_transactions(
+    document: &mut RawStruct,
+    list: &str,
+    scenario_variant: &str,
+    name: &str,
+    value: &str,
+) -> Result<()> {
+    document.update_structs(list, |txn| {
+        txn.insert(name, value);
+        Ok(())
+    })?;
+    document.update_structs("scenarios", |scenario| {
+        scenario.update_variants("changes", |change| {
+            if change.name == scenario_variant {
+                change.update_newtype(|txn| {
+                    txn.insert(name, value);
+                    Ok(())
+                })?;
+            }
+            Ok(())
+        })
+    })
+}
+
+/// Struct in the raw document: field names with their RON text, in file order
+///
+/// Unlike a generic `ron::Value`, which drops enum variant names (e.g. scenario
+/// changes) and cannot be converted back, the text of every field is kept as written.
+#[derive(Debug, Clone, Default)]
+struct RawStruct {
+    fields: Vec<(String, String)>,
+}
+
+impl RawStruct {
+    fn parse(text: &str) -> Result<Self> {
+        Ok(ron::from_str(text)?)
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets a field, replacing its value if it exists
+    fn set(&mut self, name: &str, value: String) {
+        match self.fields.iter_mut().find(|(field, _)| field == name) {
+            Some((_, old)) => *old = value,
+            None => self.fields.push((name.to_string(), value)),
+        }
+    }
+
+    /// Adds a field the file does not have yet, an existing value is kept
+    fn insert(&mut self, name: &str, value: &str) {
+        if self.get(name).is_none() {
+            self.fields.push((name.to_string(), value.to_string()));
+        }
+    }
+
+    /// Changes every struct in the list field `name`, a missing list is left alone
+    fn update_structs(
+        &mut self,
+        name: &str,
+        mut change: impl FnMut(&mut RawStruct) -> Result<()>,
+    ) -> Result<()> {
+        let Some(value) = self.get(name) else {
+            return Ok(());
+        };
+        let mut items: Vec<RawStruct> =
+            ron::from_str(value).with_context(|| format!("Invalid list '{}'", name))?;
+        for item in &mut items {
+            change(item)?;
+        }
+        let items: Vec<String> = items.iter().map(RawStruct::to_ron).collect();
+        self.set(name, format!("[{}]", items.join(", ")));
+        Ok(())
+    }
+
+    /// Changes every enum value in the list field `name`, a missing list is left alone
+    fn update_variants(
+        &mut self,
+        name: &str,
+        mut change: impl FnMut(&mut RawVariant) -> Result<()>,
+    ) -> Result<()> {
+        let Some(value) = self.get(name) else {
+            return Ok(());
+        };
+        let raw: Vec<Box<RawValue>> =
+            ron::from_str(value).with_context(|| format!("Invalid list '{}'", name))?;
+        let mut items = Vec::new();
+        for item in raw {
+            let mut variant = RawVariant::parse(item.trim().get_ron())?;
+            change(&mut variant)?;
+            items.push(variant.to_ron());
+        }
+        self.set(name, format!("[{}]", items.join(", ")));
+        Ok(())
+    }
+
+    fn fields_ron(&self) -> String {
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{}: {}", name, value))
+            .collect();
+        fields.join(", ")
+    }
+
+    fn to_ron(&self) -> String {
+        format!("({})", self.fields_ron())
+    }
+}
+
+impl<'de> Deserialize<'de> for RawStruct {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RawStructVisitor;
+
+        impl<'de> Visitor<'de> for RawStructVisitor {
+            type Value = RawStruct;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a struct")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<RawStruct, A::Error> {
+                let mut fields = Vec::new();
+                while let Some((name, value)) = map.next_entry::<String, Box<RawValue>>()? {
+                    fields.push((name, value.trim().get_ron().to_string()));
+                }
+                Ok(RawStruct { fields })
+            }
+        }
+
+        // RON reports structs (with or without a name) as maps to `deserialize_any`
+        deserializer.deserialize_any(RawStructVisitor)
+    }
+}
+
+/// Enum value in the raw document, e.g. `Remove(id: "…")` or `AddOneTime((…))`
+#[derive(Debug, Clone)]
+struct RawVariant {
+    name: String,
+    /// Text inside the parentheses, None for unit variants
+    body: Option<String>,
+}
+
+impl RawVariant {
+    fn parse(text: &str) -> Result<Self> {
+        let text = text.trim();
+        let name_len = text
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(text.len());
+        let (name, rest) = text.split_at(name_len);
+        if name.is_empty() {
+            anyhow::bail!("Expected an enum variant, found '{}'", text);
+        }
+
+        let rest = rest.trim();
+        let body = if rest.is_empty() {
+            None
+        } else {
+            let body = rest
+                .strip_prefix('(')
+                .and_then(|rest| rest.strip_suffix(')'))
+                .with_context(|| format!("Invalid enum variant '{}'", text))?;
+            Some(body.to_string())
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            body,
+        })
+    }
+
+    /// Changes the struct wrapped by a newtype variant, e.g. the transaction in `AddOneTime((…))`
+    fn update_newtype(&mut self, change: impl FnOnce(&mut RawStruct) -> Result<()>) -> Result<()> {
+        let body = self.body.as_deref().unwrap_or_default();
+        let mut inner = RawStruct::parse(body)
+            .with_context(|| format!("Invalid {} in enum variant", self.name))?;
+        change(&mut inner)?;
+        self.body = Some(inner.to_ron());
+        Ok(())
+    }
+
+    /// Changes the fields of a struct variant, e.g. `Remove(id: "…")`
+    fn update_fields(&mut self, change: impl FnOnce(&mut RawStruct) -> Result<()>) -> Result<()> {
+        let body = self.body.as_deref().unwrap_or_default();
+        let mut fields = RawStruct::parse(&format!("({})", body))
+            .with_context(|| format!("Invalid fields of enum variant {}", self.name))?;
+        change(&mut fields)?;
+        self.body = Some(fields.fields_ron());
+        Ok(())
+    }
+
+    fn to_ron(&self) -> String {
+        match &self.body {
+            Some(body) => format!("{}({})", self.name, body),
+            None => self.name.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V0_DATA: &str = r#"(
+        recurring: [
+            (
+                id: "550e8400-e29b-41d4-a716-446655440001",
+                description: "Netflix",
+                amount: "-15",
+                day_of_month: 14,
+                active: true,
+                created_at: "2025-01-01T12:00:00Z",
+            ),
+        ],
+        one_time: [
+            (
+                id: "550e8400-e29b-41d4-a716-446655440002",
+                description: "Oprava",
+                amount: "-3000",
+                date: "2025-02-01",
+                created_at: "2025-01-01T12:00:00Z",
+            ),
+        ],
+        balance_snapshots: [
+            (
+                id: "550e8400-e29b-41d4-a716-446655440003",
+                date: "2025-01-10",
+                balance: "10000",
+                created_at: "2025-01-10T12:00:00Z",
+            ),
+        ],
+    )"#;
+
+    /// Field of the first struct in a list of the document
+    fn first_item_field(document: &RawStruct, list: &str, name: &str) -> Option<String> {
+        let items: Vec<RawStruct> = ron::from_str(document.get(list)?).unwrap();
+        items.first()?.get(name).map(str::to_string)
+    }
+
+    #[test]
+    fn test_every_version_has_a_migration() {
+        assert_eq!(MIGRATIONS.len(), SCHEMA_VERSION as usize);
+    }
+
+    #[test]
+    fn test_each_step_inserts_its_fields() {
+        let mut document = RawStruct::parse(V0_DATA).unwrap();
+
+        type Check = fn(&RawStruct) -> bool;
+        let checks: [(usize, Check); 10] = [
+            (1, |d| d.get("scenarios") == Some("[]")),
+            (2, |d| {
+                first_item_field(d, "recurring", "amount_changes").as_deref() == Some("[]")
+            }),
+            (3, |d| {
+                first_item_field(d, "recurring", "installment_plan").as_deref() == Some("None")
+            }),
+            (4, |d| {
+                first_item_field(d, "recurring", "amount_range").as_deref() == Some("None")
+            }),
+            (5, |d| {
+                d.get("account")
+                    .is_some_and(|a| a.contains("interest_day: 31"))
+            }),
+            (6, |d| {
+                first_item_field(d, "recurring", "category").as_deref() == Some("None")
+                    && first_item_field(d, "one_time", "category").as_deref() == Some("None")
+            }),
+            (7, |d| d.get("budgets") == Some("[]")),
+            (8, |d| {
+                d.get("goals") == Some("[]")
+                    && first_item_field(d, "recurring", "end_date").as_deref() == Some("None")
+            }),
+            (9, |d| {
+                first_item_field(d, "balance_snapshots", "timing").as_deref() == Some("After")
+            }),
+            (10, |d| {
+                first_item_field(d, "recurring", "overrides").as_deref() == Some("[]")
+            }),
+        ];
+
+        for (step, check) in checks {
+            assert!(!check(&document), "field of step {} exists too early", step);
+            MIGRATIONS[step](&mut document).unwrap();
+            assert!(check(&document), "step {} did not insert its field", step);
+        }
+
+        // Version 1 files have no scenarios yet, so add one for the last step
+        document.set(
+            "scenarios",
+            r#"[(name: "lease", changes: [Remove(id: "550e8400-e29b-41d4-a716-446655440001"), ChangeAmount(id: "550e8400-e29b-41d4-a716-446655440002", amount: "-500")], created_at: "2025-03-04T12:00:00Z")]"#
+                .to_string(),
+        );
+        MIGRATIONS[11](&mut document).unwrap();
+        let changes = first_item_field(&document, "scenarios", "changes").unwrap();
+        assert_eq!(
+            changes.matches(r#"effective_from: "2025-03-04""#).count(),
+            2
+        );
+
+        // Untouched fields keep their text
+        assert_eq!(
+            first_item_field(&document, "recurring", "amount").as_deref(),
+            Some(r#""-15""#)
+        );
+        assert_eq!(
+            first_item_field(&document, "balance_snapshots", "balance").as_deref(),
+            Some(r#""10000""#)
+        );
+    }
+
+    #[test]
+    fn test_migrates_unversioned_file() {
+        let parsed = parse_data(V0_DATA).unwrap();
+
+        assert_eq!(parsed.original_version, 0);
+        assert!(parsed.was_migrated());
+        assert_eq!(parsed.data.schema_version, SCHEMA_VERSION);
+        assert_eq!(parsed.data.recurring.len(), 1);
+        assert_eq!(parsed.data.recurring[0].description, "Netflix");
+        assert_eq!(parsed.data.one_time[0].category, None);
+    }
+
+    #[test]
+    fn test_migrates_v2_file_with_scenarios() {
+        let contents = r#"(
+            schema_version: 2,
+            recurring: [],
+            one_time: [],
+            balance_snapshots: [],
+            scenarios: [
+                (
+                    name: "lease",
+                    changes: [
+                        Remove(id: "550e8400-e29b-41d4-a716-446655440001"),
+                        AddRecurring((
+                            id: "550e8400-e29b-41d4-a716-446655440004",
+                            description: "Parking",
+                            amount: "-1500",
+                            day_of_month: 1,
+                            active: true,
+                            created_at: "2025-01-01T12:00:00Z",
+                        )),
+                    ],
+                    created_at: "2025-01-01T12:00:00Z",
+                ),
+            ],
+        )"#;
+        let parsed = parse_data(contents).unwrap();
+
+        assert_eq!(parsed.original_version, 2);
+        assert_eq!(parsed.data.schema_version, SCHEMA_VERSION);
+        let changes = &parsed.data.scenarios[0].changes;
+        assert_eq!(changes.len(), 2);
+        match &changes[0] {
+            crate::models::ScenarioChange::Remove { effective_from, .. } => {
+                assert_eq!(effective_from.to_string(), "2025-01-01");
+            }
+            other => panic!("expected Remove, got {:?}", other),
+        }
+        match &changes[1] {
+            crate::models::ScenarioChange::AddRecurring(txn) => {
+                assert_eq!(txn.description, "Parking");
+                assert!(txn.amount_changes.is_empty());
+                assert!(txn.overrides.is_empty());
+            }
+            other => panic!("expected AddRecurring, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_raw_variant_round_trip() {
+        let unit = RawVariant::parse(" After ").unwrap();
+        assert_eq!(unit.to_ron(), "After");
+
+        let mut change = RawVariant::parse(r#"AddOneTime((description: "Oprava"))"#).unwrap();
+        change
+            .update_newtype(|txn| {
+                txn.insert("category", "None");
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(
+            change.to_ron(),
+            r#"AddOneTime((description: "Oprava", category: None))"#
+        );
+    }
+
+    #[test]
+    fn test_current_version_is_not_migrated() {
+        let data = CashflowData::default();
+        let contents = ron::to_string(&data).unwrap();
+        let parsed = parse_data(&contents).unwrap();
+
+        assert_eq!(parsed.original_version, SCHEMA_VERSION);
+        assert!(!parsed.was_migrated());
+    }
+
+    #[test]
+    fn test_refuses_newer_version() {
+        let contents = format!(
+            "(schema_version: {}, recurring: [], one_time: [], balance_snapshots: [])",
+            SCHEMA_VERSION + 1
+        );
+        let err = parse_data(&contents).unwrap_err();
+
+        assert!(err.to_string().contains("upgrade cashflow"));
+    }
+}
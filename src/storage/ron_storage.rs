@@ -1,12 +1,14 @@
 use crate::Result;
 use crate::config;
-use crate::models::CashflowData;
+use crate::models::{CashflowData, SCHEMA_VERSION, validate_data};
+use crate::storage::{load_archive, parse_data, prune_data, save_archive};
 use anyhow::Context;
+use chrono::{Duration, Local};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs as async_fs;
 
-/// Gets the path to the Cashflow data directory (from config or default ~/.cashflow)
+/// Gets the path to the Cashflow data directory (from config or default $XDG_DATA_HOME/cashflow)
 pub async fn get_data_dir() -> Result<PathBuf> {
     config::get_data_dir().await
 }
@@ -27,29 +29,94 @@ pub async fn ensure_data_dir() -> Result<PathBuf> {
 
 /// Loads CashflowData from the RON file
 /// Returns empty data if file doesn't exist
+/// Prints a warning if the data contains problems (see `cashflow doctor`)
 pub async fn load_data() -> Result<CashflowData> {
+    let data = load_data_unvalidated().await?;
+
+    let issues = validate_data(&data);
+    if !issues.is_empty() {
+        eprintln!(
+            "⚠️  Data file has {} problem(s), run `cashflow doctor` for details",
+            issues.len()
+        );
+    }
+
+    Ok(data)
+}
+
+/// Loads CashflowData from the RON file without validating it
+/// Files with an older schema are backed up, migrated and written back
+pub async fn load_data_unvalidated() -> Result<CashflowData> {
+    read_ron_file(&get_ron_path().await?).await
+}
+
+/// Saves CashflowData to the RON file atomically
+/// Applies the automatic pruning policy from config, if any, to the saved data, so
+/// entries are only archived by commands that write anyway (the server holds its write lock)
+pub async fn save_data(data: &CashflowData) -> Result<()> {
+    ensure_data_dir().await?;
     let ron_path = get_ron_path().await?;
 
+    let Some(days) = config::load_config().await?.auto_prune_days else {
+        return write_ron_file(&ron_path, data).await;
+    };
+
+    let before = Local::now().date_naive() - Duration::days(days as i64);
+    let mut pruned = data.clone();
+    let mut archive = load_archive().await?;
+    let summary = prune_data(&mut pruned, &mut archive, before);
+
+    if !summary.is_empty() {
+        // Write the archive first so nothing is lost if saving data fails
+        save_archive(&archive).await?;
+        eprintln!(
+            "Archived {} one-time transaction(s) and {} balance snapshot(s) older than {} days",
+            summary.one_time, summary.balance_snapshots, days
+        );
+    }
+
+    write_ron_file(&ron_path, &pruned).await
+}
+
+/// Reads a RON file in the CashflowData format
+/// Returns empty data if file doesn't exist
+/// Files with an older schema are backed up, migrated and written back
+pub(crate) async fn read_ron_file(ron_path: &Path) -> Result<CashflowData> {
     if !ron_path.exists() {
         // Return empty data on first run
         return Ok(CashflowData::default());
     }
 
-    let contents = async_fs::read_to_string(&ron_path)
+    let contents = async_fs::read_to_string(ron_path)
         .await
-        .context("Failed to read data file")?;
-
-    let data: CashflowData = ron::from_str(&contents).context("Failed to parse RON data file")?;
+        .with_context(|| format!("Failed to read {}", ron_path.display()))?;
+
+    let parsed = parse_data(&contents)?;
+
+    if parsed.was_migrated() {
+        // Keep the original file around before overwriting it with the new format
+        let backup_path = ron_path.with_extension(format!("ron.v{}.bak", parsed.original_version));
+        async_fs::copy(ron_path, &backup_path)
+            .await
+            .context("Failed to write data file backup before migration")?;
+
+        write_ron_file(ron_path, &parsed.data).await?;
+
+        eprintln!(
+            "Upgraded {} from schema version {} to {} (backup: {})",
+            ron_path.display(),
+            parsed.original_version,
+            SCHEMA_VERSION,
+            backup_path.display()
+        );
+    }
 
-    Ok(data)
+    Ok(parsed.data)
 }
 
-/// Saves CashflowData to the RON file atomically
+/// Writes CashflowData to a RON file atomically
 /// Uses write-then-rename pattern to prevent corruption
-pub async fn save_data(data: &CashflowData) -> Result<()> {
-    ensure_data_dir().await?;
-
-    let ron_path = get_ron_path().await?;
+pub(crate) async fn write_ron_file(ron_path: &Path, data: &CashflowData) -> Result<()> {
     let tmp_path = ron_path.with_extension("ron.tmp");
 
     // Serialize to RON with pretty formatting
@@ -67,7 +134,7 @@ pub async fn save_data(data: &CashflowData) -> Result<()> {
         .context("Failed to write temporary data file")?;
 
     // Atomic rename
-    async_fs::rename(&tmp_path, &ron_path)
+    async_fs::rename(&tmp_path, ron_path)
         .await
         .context("Failed to rename temporary file to data file")?;
 